@@ -0,0 +1,129 @@
+use charsheet_core::character::Character;
+use charsheet_core::common::{Ability, CharError};
+use charsheet_core::level;
+
+pub const USAGE: &str = "usage:
+  charsheet new [--name NAME] [--race RACE] [--class CLASS] [--str N] [--str-percentile N]
+                [--dex N] [--con N] [--int N] [--wis N] [--cha N] [--xp N]
+      print a new character as JSON
+  charsheet show FILE
+      print the computed sheet of a saved or exported character
+  charsheet validate FILE
+      check a character against the rules
+
+FILE may be - to read from stdin. show and validate exit with 1 if the character breaks the
+rules, every command exits with 2 on bad arguments or unreadable files.";
+
+#[derive(Debug)]
+pub enum Command {
+    New(Box<Character>),
+    Show(String),
+    Validate(String),
+    Help,
+}
+
+/// Parses the arguments after the program name
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    match args.split_first() {
+        Some((cmd, rest)) if cmd == "new" => parse_new(rest).map(|c| Command::New(Box::new(c))),
+        Some((cmd, rest)) if cmd == "show" => parse_file(cmd, rest).map(Command::Show),
+        Some((cmd, rest)) if cmd == "validate" => parse_file(cmd, rest).map(Command::Validate),
+        Some((cmd, _)) if cmd == "help" || cmd == "--help" || cmd == "-h" => Ok(Command::Help),
+        Some((cmd, _)) => Err(format!("unknown command {}", cmd)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_file(cmd: &str, args: &[String]) -> Result<String, String> {
+    match args {
+        [file] => Ok(file.clone()),
+        [] => Err(format!("{} needs a FILE", cmd)),
+        _ => Err(format!("{} takes a single FILE", cmd)),
+    }
+}
+
+/// Builds a character from `--flag value` pairs. Values that aren't even the right type are
+/// rejected here; whether the character follows the rules is left to `Character::validate`.
+fn parse_new(args: &[String]) -> Result<Character, String> {
+    let mut character = Character::default();
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags
+            .next()
+            .ok_or_else(|| format!("{} needs a value", flag))?;
+        match flag.as_str() {
+            "--name" => character.char_name = value.clone(),
+            "--race" => character.race = value.parse().map_err(|e: CharError| e.to_string())?,
+            "--class" => {
+                character.char_class = value.parse().map_err(|e: CharError| e.to_string())?
+            }
+            "--str-percentile" => {
+                let per = value
+                    .parse::<i32>()
+                    .map_err(|e| CharError::PercentParseError(e).to_string())?;
+                character.str_percentile = Some(per);
+            }
+            "--xp" => character.xp = level::parse_xp(value).map_err(|e| e.to_string())?,
+            _ => {
+                let ability = Ability::all()
+                    .into_iter()
+                    .find(|a| flag.trim_start_matches("--") == a.field_name())
+                    .ok_or_else(|| format!("unknown flag {}", flag))?;
+                *character.ability_mut(ability) = value
+                    .parse::<i32>()
+                    .map_err(|e| CharError::AbilityParseError(ability, e).to_string())?;
+            }
+        }
+    }
+    Ok(character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use charsheet_core::class::CharacterClass;
+    use charsheet_core::race::Race;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn new_from_flags() {
+        let cmd = parse(&args(
+            "new --name Tordek --race Dwarf --class Fighter --str 18 --str-percentile 76 --con 17 --xp 2500",
+        ))
+        .unwrap();
+        match cmd {
+            Command::New(character) => {
+                assert_eq!(character.char_name, "Tordek");
+                assert_eq!(character.race, Race::Dwarf);
+                assert_eq!(character.char_class, CharacterClass::Fighter);
+                assert_eq!(character.str, 18);
+                assert_eq!(character.str_percentile, Some(76));
+                assert_eq!(character.con, 17);
+                assert_eq!(character.xp, 2_500);
+                assert_eq!(character.dex, 0);
+            }
+            other => panic!("expected New, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bad_arguments() {
+        assert!(parse(&args("")).is_err());
+        assert!(parse(&args("roll")).is_err());
+        assert!(parse(&args("show")).is_err());
+        assert!(parse(&args("validate a.json b.json")).is_err());
+        assert!(parse(&args("new --str")).is_err());
+        assert!(parse(&args("new --luck 12")).is_err());
+        assert_eq!(
+            parse(&args("new --dex high")).unwrap_err(),
+            "Dexterity must be a number (invalid digit found in string)"
+        );
+        assert_eq!(
+            parse(&args("new --class Samurai")).unwrap_err(),
+            "invalid class:Samurai"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::args::{Command, USAGE};
+use charsheet_core::character::Character;
+use charsheet_core::common::CharError;
+use charsheet_core::save_format;
+use std::io::Read;
+use std::process;
+
+mod args;
+mod sheet;
+
+/// The character breaks the rules
+const EXIT_INVALID: i32 = 1;
+/// Bad arguments, or the file couldn't be read at all
+const EXIT_USAGE: i32 = 2;
+
+fn read_input(path: &str) -> Result<String, String> {
+    let mut json = String::new();
+    let read = if path == "-" {
+        std::io::stdin().read_to_string(&mut json).map(|_| ())
+    } else {
+        std::fs::File::open(path).and_then(|mut file| file.read_to_string(&mut json).map(|_| ()))
+    };
+    read.map_err(|e| format!("couldn't read {}: {}", path, e))?;
+    Ok(json)
+}
+
+fn report(errors: &[CharError]) {
+    for e in errors {
+        eprintln!("{}", e);
+    }
+}
+
+/// Loads a character, exiting with `EXIT_USAGE` if it can't be read. Rule errors are returned.
+fn load(path: &str) -> (Character, Vec<CharError>) {
+    let json = read_input(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_USAGE);
+    });
+    Character::from_json_str(&json).unwrap_or_else(|errors| {
+        report(&errors);
+        process::exit(EXIT_USAGE);
+    })
+}
+
+fn run(cmd: Command) -> i32 {
+    let errors = match cmd {
+        Command::New(character) => {
+            match save_format::to_json(&character) {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return EXIT_USAGE;
+                }
+            }
+            character.validate()
+        }
+        Command::Show(path) => {
+            let (character, errors) = load(&path);
+            println!("{}", sheet::render(&character));
+            errors
+        }
+        Command::Validate(path) => load(&path).1,
+        Command::Help => {
+            println!("{}", USAGE);
+            vec![]
+        }
+    };
+    if errors.is_empty() {
+        0
+    } else {
+        report(&errors);
+        EXIT_INVALID
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args::parse(&args) {
+        Ok(cmd) => process::exit(run(cmd)),
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    }
+}
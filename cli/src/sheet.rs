@@ -0,0 +1,355 @@
+use charsheet_core::catalog;
+use charsheet_core::character::Character;
+use charsheet_core::combat::WeaponSkill;
+use charsheet_core::common::{Ability, Result};
+use std::fmt::Display;
+
+/// Formats a table lookup the way the browser sheet does, with failed lookups shown inline
+fn entry<T: Display>(label: &str, value: Result<T>) -> String {
+    match value {
+        Ok(v) => format!("  {}: {}", label, v),
+        Err(e) => format!("  {}: Err! {}", label, e),
+    }
+}
+
+fn or_dash<T: Display>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+/// Heading for one ability, e.g. `Strength 18/76` or `Constitution 18 (rolled 17)`
+fn score(character: &Character, ability: Ability) -> String {
+    let rolled = character.ability(ability);
+    let val = character.effective_ability(ability);
+    let mut heading = format!("{} {}", ability, val);
+    if ability == Ability::Str && val == 18 {
+        if let Some(per) = character.str_percentile {
+            heading = format!("{}/{:02}", heading, per % 100);
+        }
+    }
+    if rolled != val {
+        heading = format!("{} (rolled {})", heading, rolled);
+    }
+    heading
+}
+
+fn ability_lines(character: &Character, ability: Ability) -> Vec<String> {
+    match ability {
+        Ability::Str => {
+            let s = character.strength();
+            vec![
+                entry("Hit Adj", s.hit_adj()),
+                entry("Damage Adj", s.damage_adj()),
+                entry("Weight Allow", s.weight_allow()),
+                entry("Max Press", s.max_press()),
+                entry("Open Doors", s.open_doors()),
+                entry("Bend Bars", s.bend_bars().map(|v| format!("{}%", v))),
+            ]
+        }
+        Ability::Dex => {
+            let d = character.dexterity();
+            vec![
+                entry("Reaction Adj", d.reaction_adj()),
+                entry("Missile Attack Adj", d.missile_attack_adj()),
+                entry("Defensive Adj", d.defensive_adj()),
+            ]
+        }
+        Ability::Con => {
+            let c = character.constitution();
+            vec![
+                entry("HP Adj", c.hp_adj()),
+                entry("System Shock", c.system_shock().map(|v| format!("{}%", v))),
+                entry(
+                    "Resurrection Survival",
+                    c.resurrection_survival().map(|v| format!("{}%", v)),
+                ),
+                entry("Poison Save", c.poison_save()),
+                entry(
+                    "Regeneration",
+                    c.regeneration().map(|v| match v {
+                        Some(turns) => format!("1/{} turns", turns),
+                        None => "Nil".to_string(),
+                    }),
+                ),
+            ]
+        }
+        Ability::Int => {
+            let i = character.intelligence();
+            vec![
+                entry("Languages", i.languages()),
+                entry("Spell Level", i.max_spell_level().map(or_dash)),
+                entry(
+                    "Chance to Learn",
+                    i.chance_to_learn()
+                        .map(|v| v.map(|v| format!("{}%", v)))
+                        .map(or_dash),
+                ),
+                entry("Max Spells/Level", i.max_spells_per_level().map(or_dash)),
+                entry("Illusion Immunity", i.illusion_immunity().map(or_dash)),
+            ]
+        }
+        Ability::Wis => {
+            let w = character.wisdom();
+            vec![
+                entry("Magic Defense Adj", w.magical_defense_adj()),
+                entry(
+                    "Bonus Spells",
+                    w.bonus_spells().map(|v| {
+                        list(
+                            v.iter()
+                                .enumerate()
+                                .filter(|(_, n)| **n > 0)
+                                .map(|(level, n)| format!("L{}x{}", level + 1, n))
+                                .collect(),
+                        )
+                    }),
+                ),
+                entry("Spell Failure", w.spell_failure().map(|v| format!("{}%", v))),
+                entry(
+                    "Spell Immunity",
+                    w.spell_immunity()
+                        .map(|v| list(v.into_iter().map(String::from).collect())),
+                ),
+            ]
+        }
+        Ability::Cha => {
+            let c = character.charisma();
+            vec![
+                entry("Max Henchmen", c.max_henchmen()),
+                entry("Loyalty Base", c.loyalty_base()),
+                entry("Reaction Adj", c.reaction_adj()),
+            ]
+        }
+    }
+}
+
+/// The whole computed sheet as plain text
+pub fn render(character: &Character) -> String {
+    let race = character.race;
+    let class = character.char_class;
+    let name = if character.char_name.is_empty() {
+        "(unnamed)"
+    } else {
+        character.char_name.as_str()
+    };
+    let mut lines = vec![
+        name.to_string(),
+        format!("{} {} ({})", race, class, class.group()),
+        format!(
+            "  Infravision: {}",
+            or_dash(race.infravision().map(|v| format!("{}'", v)))
+        ),
+        format!(
+            "  Ability Adj: {}",
+            list(
+                Ability::all()
+                    .into_iter()
+                    .filter(|a| race.ability_adjustment(*a) != 0)
+                    .map(|a| format!("{} {:+}", a, race.ability_adjustment(a)))
+                    .collect()
+            )
+        ),
+        format!(
+            "  Special Abilities: {}",
+            or_dash(Some(race.special_abilities().join("; ")).filter(|s| !s.is_empty()))
+        ),
+    ];
+    lines.push(String::new());
+    lines.push(format!("Level {}", character.level()));
+    lines.push(format!(
+        "  Experience: {} (next level at {})",
+        character.xp,
+        or_dash(character.next_level_xp())
+    ));
+    if character.xp_bonus() {
+        lines.push("  Experience Bonus: 10%".to_string());
+    }
+    if character.hit_dice_owed() > 0 {
+        lines.push(format!("  Hit Dice to Roll: {}", character.hit_dice_owed()));
+    }
+    lines.push(format!(
+        "Hit Points: {}/{}",
+        character.current_hp(),
+        character.max_hp()
+    ));
+    if character.hit_points.temporary > 0 {
+        lines.push(format!("  Temporary: {}", character.hit_points.temporary));
+    }
+    lines.push(format!(
+        "  Hit Dice: {}",
+        list(
+            character
+                .hit_points
+                .rolls
+                .iter()
+                .map(|r| r.to_string())
+                .collect()
+        )
+    ));
+    if let Some(method) = character.generation_method {
+        lines.push(format!(
+            "  Scores: Method {} ({})",
+            method,
+            method.description()
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("Combat".to_string());
+    lines.push(format!("  THAC0: {}", character.thac0()));
+    lines.push(format!(
+        "  Melee: {}",
+        character.melee_attack(WeaponSkill::Proficient)
+    ));
+    lines.push(format!(
+        "  Missile: {}",
+        character.missile_attack(WeaponSkill::Proficient)
+    ));
+    lines.push(format!(
+        "  Non-proficient: {:+}",
+        class.group().non_proficiency_penalty()
+    ));
+    lines.push(format!(
+        "  Weapon Proficiencies: {} ({} of {} slots used)",
+        character.proficiencies,
+        character.weapon_slots_used(),
+        character.weapon_slots()
+    ));
+    for attack in character.weapon_attacks() {
+        lines.push(format!("  {}", attack));
+    }
+
+    let ac = character.armor_class();
+    lines.push(String::new());
+    lines.push(ac.to_string());
+    lines.push(format!("  Shieldless: {}", ac.shieldless()));
+    lines.push(format!("  Rear: {}", ac.rear()));
+    lines.push(format!("  Surprised: {}", ac.surprised()));
+
+    lines.push(String::new());
+    lines.push("Saving Throws".to_string());
+    for save in character.saving_throws() {
+        lines.push(format!("  {}: {}", save.category, save));
+    }
+
+    let inventory = &character.inventory;
+    lines.push(String::new());
+    lines.push(format!(
+        "Inventory: {:.1} lb carried, {:.1} lb on mount, worth {}",
+        inventory.carried_weight(),
+        inventory.mount_weight(),
+        inventory
+            .value()
+            .map_or_else(|e| format!("Err! {}", e), catalog::format_cost)
+    ));
+    for item in &inventory.items {
+        lines.push(format!("  {}", item));
+    }
+
+    let purse = &character.purse;
+    lines.push(String::new());
+    lines.push(format!(
+        "Purse: {} (worth {}), {:.1} lb",
+        purse,
+        purse
+            .value()
+            .map_or_else(|e| format!("Err! {}", e), catalog::format_cost),
+        purse.weight()
+    ));
+    for valuable in &purse.valuables {
+        lines.push(format!("  {}", valuable));
+    }
+    for transaction in &purse.transactions {
+        lines.push(format!("  {}", transaction));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Load: {:.1} lb", character.load()));
+    lines.push(format!("  Encumbrance: {}", character.encumbrance()));
+    lines.push(format!("  Movement: {}", character.movement()));
+
+    for ability in Ability::all() {
+        lines.push(String::new());
+        if character.ability(ability) == 0 {
+            lines.push(format!("{} -", ability));
+            continue;
+        }
+        lines.push(score(character, ability));
+        lines.extend(ability_lines(character, ability));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use charsheet_core::armor_class::{ArmorType, Protection, ShieldType};
+    use charsheet_core::class::CharacterClass;
+    use charsheet_core::inventory::Location;
+    use charsheet_core::purse::{Purse, Valuable, ValuableKind};
+    use charsheet_core::race::Race;
+
+    #[test]
+    fn renders_derived_scores() {
+        let mut character = Character {
+            char_name: "Tordek".to_string(),
+            char_class: CharacterClass::Fighter,
+            race: Race::Dwarf,
+            str: 18,
+            str_percentile: Some(100),
+            con: 17,
+            xp: 2_500,
+            protection: Protection {
+                armor: ArmorType::ChainMail,
+                shield: ShieldType::Medium,
+                ..Default::default()
+            },
+            purse: Purse {
+                gp: 30,
+                valuables: vec![Valuable {
+                    kind: ValuableKind::Gem,
+                    description: "Pearl".to_string(),
+                    value: 10_000,
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        character.buy_item("Long Sword", 1, Location::Carried).unwrap();
+        character.learn_weapon("Long Sword").unwrap();
+        character.learn_weapon("Axes").unwrap();
+        character.specialize("Long Sword").unwrap();
+        character.add_item("Torch", 3, Location::Pack).unwrap();
+        let sheet = render(&character);
+        assert!(sheet.contains(
+            "\nInventory: 7.0 lb carried, 0.0 lb on mount, worth 15 gp 3 cp\n  Long Sword (Carried)\n  Torch x3 (Pack)\n"
+        ));
+        assert!(sheet.contains(
+            "\nPurse: 15 gp (worth 15 gp), 0.3 lb\n  Gem: Pearl (100 gp)\n  Bought Long Sword for 15 gp, 15 gp left\n"
+        ));
+        assert!(sheet.contains("\nLoad: 7.3 lb\n  Encumbrance: Unencumbered\n  Movement: 6\n"));
+        assert!(sheet.starts_with("Tordek\nDwarf Fighter (Warrior)\n"));
+        assert!(sheet.contains("\nLevel 2\n  Experience: 2500 (next level at 4000)\n"));
+        assert!(sheet.contains("\n  Experience Bonus: 10%\n  Hit Dice to Roll: 2\n"));
+        assert!(sheet.contains(
+            "\nCombat\n  THAC0: 19\n  Melee: THAC0 16 (base 19, Str +3), damage +6 (Str +6)\n"
+        ));
+        assert!(sheet.contains(
+            "\n  Non-proficient: -2\n  Weapon Proficiencies: Long Sword (Specialized), Axes (tight group) (4 of 4 slots used)\n  Long Sword (Specialized): THAC0 15 (base 19, Str +3, Specialized +1), damage +8 (Str +6, Specialized +2), 3/2 attacks per round\n\nAC "
+        ));
+        assert!(sheet.contains("\n  Paralyzation/Poison/Death: 14 (+5 vs poison)\n"));
+        assert!(sheet.contains("\n  Spell: 12\n"));
+        assert!(sheet.contains("\nAC 4 (Chain Mail -5, Medium Shield -1)\n  Shieldless: 5\n"));
+        assert!(sheet.contains("\nStrength 18/00\n  Hit Adj: 3\n  Damage Adj: 6\n"));
+        assert!(sheet.contains("\n  Open Doors: 16(6)\n"));
+        assert!(sheet.contains("\nConstitution 18 (rolled 17)\n  HP Adj: 4\n"));
+        assert!(sheet.contains("\nDexterity -\n"));
+    }
+}
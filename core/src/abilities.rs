@@ -0,0 +1,1114 @@
+use crate::common::CharError::{InvalidAbility, InvalidPercentile};
+use crate::common::{Ability, CharError, Result};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+pub struct StrengthPercentile {
+    pub str: i32,
+    pub per: Option<i32>,
+    pub err: Option<CharError>,
+}
+
+impl StrengthPercentile {
+    pub fn hit_adj(&self) -> Result<i32> {
+        match self.str {
+            1 => Ok(-5),
+            2 | 3 => Ok(-3),
+            4 | 5 => Ok(-2),
+            6 | 7 => Ok(-1),
+            8..=16 => Ok(0),
+            17 => Ok(1),
+            18 => {
+                if let Some(p) = self.per {
+                    match p {
+                        1..=50 => Ok(1),
+                        51..=99 => Ok(2),
+                        100 => Ok(3),
+                        err => Err(CharError::InvalidPercentile(Some(err))),
+                    }
+                } else {
+                    Ok(1)
+                }
+            }
+            19 | 20 => Ok(3),
+            21 | 22 => Ok(4),
+            23 => Ok(5),
+            24 => Ok(6),
+            25 => Ok(7),
+            err => Err(CharError::InvalidAbility(Ability::Str, err)),
+        }
+    }
+    pub fn damage_adj(&self) -> Result<i32> {
+        match self.str {
+            1 => Ok(-4),
+            2 => Ok(-2),
+            3 => Ok(-1),
+            4 | 5 => Ok(-1),
+            6 | 7 => Ok(0),
+            8 | 9 => Ok(0),
+            10 | 11 => Ok(0),
+            12 | 13 => Ok(0),
+            14 | 15 => Ok(0),
+            16 => Ok(1),
+            17 => Ok(1),
+            18 => {
+                if let Some(p) = self.per {
+                    match p {
+                        1..=50 => Ok(3),
+                        51..=75 => Ok(3),
+                        76..=90 => Ok(4),
+                        91..=99 => Ok(5),
+                        100 => Ok(6),
+                        i => Err(InvalidPercentile(Some(i))),
+                    }
+                } else {
+                    Ok(2)
+                }
+            }
+            19 => Ok(7),
+            20 => Ok(8),
+            21 => Ok(9),
+            22 => Ok(10),
+            23 => Ok(11),
+            24 => Ok(12),
+            25 => Ok(14),
+            i => Err(InvalidAbility(Ability::Str, i)),
+        }
+    }
+    pub fn weight_allow(&self) -> Result<i32> {
+        match self.str {
+            1 => Ok(1),
+            2 => Ok(1),
+            3 => Ok(5),
+            4 | 5 => Ok(10),
+            6 | 7 => Ok(20),
+            8 | 9 => Ok(35),
+            10 | 11 => Ok(40),
+            12 | 13 => Ok(45),
+            14 | 15 => Ok(55),
+            16 => Ok(70),
+            17 => Ok(85),
+            18 => {
+                if let Some(p) = self.per {
+                    match p {
+                        1..=50 => Ok(135),
+                        51..=75 => Ok(160),
+                        76..=90 => Ok(185),
+                        91..=99 => Ok(235),
+                        100 => Ok(335),
+                        i => Err(InvalidPercentile(Some(i))),
+                    }
+                } else {
+                    Ok(110)
+                }
+            }
+            19 => Ok(485),
+            20 => Ok(535),
+            21 => Ok(635),
+            22 => Ok(785),
+            23 => Ok(935),
+            24 => Ok(1235),
+            25 => Ok(1535),
+            i => Err(InvalidAbility(Ability::Str, i)),
+        }
+    }
+    pub fn max_press(&self) -> Result<i32> {
+        match self.str {
+            1 => Ok(3),
+            2 => Ok(5),
+            3 => Ok(10),
+            4 | 5 => Ok(25),
+            6 | 7 => Ok(55),
+            8 | 9 => Ok(90),
+            10 | 11 => Ok(115),
+            12 | 13 => Ok(140),
+            14 | 15 => Ok(170),
+            16 => Ok(195),
+            17 => Ok(220),
+            18 => {
+                if let Some(p) = self.per {
+                    match p {
+                        1..=50 => Ok(280),
+                        51..=75 => Ok(305),
+                        76..=90 => Ok(330),
+                        91..=99 => Ok(380),
+                        100 => Ok(480),
+                        i => Err(InvalidPercentile(Some(i))),
+                    }
+                } else {
+                    Ok(255)
+                }
+            }
+            19 => Ok(640),
+            20 => Ok(700),
+            21 => Ok(810),
+            22 => Ok(970),
+            23 => Ok(1130),
+            24 => Ok(1440),
+            25 => Ok(1750),
+            i => Err(InvalidAbility(Ability::Str, i)),
+        }
+    }
+    /// The heaviest loads, in pounds, that leave the character unencumbered, lightly, moderately
+    /// and heavily encumbered. Anything more up to the maximum press is severe.
+    pub fn encumbrance_limits(&self) -> Result<[i32; 4]> {
+        match self.str {
+            1 | 2 => Ok([1, 2, 3, 4]),
+            3 => Ok([5, 6, 7, 9]),
+            4 | 5 => Ok([10, 13, 16, 19]),
+            6 | 7 => Ok([20, 29, 38, 46]),
+            8 | 9 => Ok([35, 50, 65, 80]),
+            10 | 11 => Ok([40, 58, 76, 96]),
+            12 | 13 => Ok([45, 69, 93, 117]),
+            14 | 15 => Ok([55, 85, 115, 145]),
+            16 => Ok([70, 100, 130, 160]),
+            17 => Ok([85, 121, 157, 193]),
+            // from 18 up every band is 39 lb wide
+            _ => {
+                let allow = self.weight_allow()?;
+                Ok([allow, allow + 39, allow + 78, allow + 117])
+            }
+        }
+    }
+    pub fn open_doors(&self) -> Result<OpenDoors> {
+        let od = |normal, locked| Ok(OpenDoors { normal, locked });
+        match self.str {
+            1 | 2 => od(1, None),
+            3 => od(2, None),
+            4 | 5 => od(3, None),
+            6 | 7 => od(4, None),
+            8 | 9 => od(5, None),
+            10 | 11 => od(6, None),
+            12 | 13 => od(7, None),
+            14 | 15 => od(8, None),
+            16 => od(9, None),
+            17 => od(10, None),
+            18 => {
+                if let Some(p) = self.per {
+                    match p {
+                        1..=50 => od(12, None),
+                        51..=75 => od(13, None),
+                        76..=90 => od(14, None),
+                        91..=99 => od(15, Some(3)),
+                        100 => od(16, Some(6)),
+                        i => Err(InvalidPercentile(Some(i))),
+                    }
+                } else {
+                    od(11, None)
+                }
+            }
+            19 => od(16, Some(8)),
+            20 => od(17, Some(10)),
+            21 => od(17, Some(12)),
+            22 => od(18, Some(14)),
+            23 => od(18, Some(16)),
+            24 => od(19, Some(17)),
+            25 => od(19, Some(18)),
+            i => Err(InvalidAbility(Ability::Str, i)),
+        }
+    }
+    /// Percent chance to bend bars or lift gates
+    pub fn bend_bars(&self) -> Result<i32> {
+        match self.str {
+            1..=7 => Ok(0),
+            8 | 9 => Ok(1),
+            10 | 11 => Ok(2),
+            12 | 13 => Ok(4),
+            14 | 15 => Ok(7),
+            16 => Ok(10),
+            17 => Ok(13),
+            18 => {
+                if let Some(p) = self.per {
+                    match p {
+                        1..=50 => Ok(20),
+                        51..=75 => Ok(25),
+                        76..=90 => Ok(30),
+                        91..=99 => Ok(35),
+                        100 => Ok(40),
+                        i => Err(InvalidPercentile(Some(i))),
+                    }
+                } else {
+                    Ok(16)
+                }
+            }
+            19 => Ok(50),
+            20 => Ok(60),
+            21 => Ok(70),
+            22 => Ok(80),
+            23 => Ok(90),
+            24 => Ok(95),
+            25 => Ok(99),
+            i => Err(InvalidAbility(Ability::Str, i)),
+        }
+    }
+}
+
+/// Open Doors roll (on a d20). `locked` is the chance to force open a locked, barred or
+/// wizard-locked door, which only the strongest characters have.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenDoors {
+    pub normal: i32,
+    pub locked: Option<i32>,
+}
+
+impl Display for OpenDoors {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self.locked {
+            Some(locked) => write!(f, "{}({})", self.normal, locked),
+            None => write!(f, "{}", self.normal),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Dexterity {
+    pub dex: i32,
+    pub err: Option<CharError>,
+}
+
+impl Dexterity {
+    pub fn reaction_adj(&self) -> Result<i32> {
+        match self.dex {
+            1 => Ok(-6),
+            2 => Ok(-4),
+            3 => Ok(-3),
+            4 => Ok(-2),
+            5 => Ok(-1),
+            6..=15 => Ok(0),
+            16 => Ok(1),
+            17 | 18 => Ok(2),
+            19 | 20 => Ok(3),
+            21..=23 => Ok(4),
+            24 | 25 => Ok(5),
+            i => Err(InvalidAbility(Ability::Dex, i)),
+        }
+    }
+    pub fn missile_attack_adj(&self) -> Result<i32> {
+        match self.dex {
+            1 => Ok(-6),
+            2 => Ok(-4),
+            3 => Ok(-3),
+            4 => Ok(-2),
+            5 => Ok(-1),
+            6..=15 => Ok(0),
+            16 => Ok(1),
+            17 | 18 => Ok(2),
+            19 | 20 => Ok(3),
+            21..=23 => Ok(4),
+            24 | 25 => Ok(5),
+            i => Err(InvalidAbility(Ability::Dex, i)),
+        }
+    }
+    pub fn defensive_adj(&self) -> Result<i32> {
+        match self.dex {
+            1 | 2 => Ok(5),
+            3 => Ok(4),
+            4 => Ok(3),
+            5 => Ok(2),
+            6 => Ok(1),
+            7..=14 => Ok(0),
+            15 => Ok(-1),
+            16 => Ok(-2),
+            17 => Ok(-3),
+            18..=20 => Ok(-4),
+            21..=23 => Ok(-5),
+            24 | 25 => Ok(-6),
+            i => Err(InvalidAbility(Ability::Dex, i)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Constitution {
+    pub con: i32,
+    pub warrior: bool,
+    pub err: Option<CharError>,
+}
+
+impl Constitution {
+    /// Hit point adjustment per hit die. Scores of 17 and up give warriors a higher bonus.
+    pub fn hp_adj(&self) -> Result<i32> {
+        match self.con {
+            1 => Ok(-3),
+            2 | 3 => Ok(-2),
+            4..=6 => Ok(-1),
+            7..=14 => Ok(0),
+            15 => Ok(1),
+            16 => Ok(2),
+            17..=25 if !self.warrior => Ok(2),
+            17 => Ok(3),
+            18 => Ok(4),
+            19 | 20 => Ok(5),
+            21..=23 => Ok(6),
+            24 | 25 => Ok(7),
+            i => Err(InvalidAbility(Ability::Con, i)),
+        }
+    }
+    pub fn system_shock(&self) -> Result<i32> {
+        match self.con {
+            1..=13 => Ok(20 + 5 * self.con),
+            14 => Ok(88),
+            15 => Ok(90),
+            16 => Ok(95),
+            17 => Ok(97),
+            18..=24 => Ok(99),
+            25 => Ok(100),
+            i => Err(InvalidAbility(Ability::Con, i)),
+        }
+    }
+    pub fn resurrection_survival(&self) -> Result<i32> {
+        match self.con {
+            1..=13 => Ok(25 + 5 * self.con),
+            14 => Ok(92),
+            15 => Ok(94),
+            16 => Ok(96),
+            17 => Ok(98),
+            18..=25 => Ok(100),
+            i => Err(InvalidAbility(Ability::Con, i)),
+        }
+    }
+    pub fn poison_save(&self) -> Result<i32> {
+        match self.con {
+            1 => Ok(-2),
+            2 => Ok(-1),
+            3..=18 => Ok(0),
+            19 | 20 => Ok(1),
+            21 | 22 => Ok(2),
+            23 | 24 => Ok(3),
+            25 => Ok(4),
+            i => Err(InvalidAbility(Ability::Con, i)),
+        }
+    }
+    /// Number of turns it takes to regenerate 1 hit point, if any
+    pub fn regeneration(&self) -> Result<Option<i32>> {
+        match self.con {
+            1..=19 => Ok(None),
+            20..=25 => Ok(Some(26 - self.con)),
+            i => Err(InvalidAbility(Ability::Con, i)),
+        }
+    }
+}
+
+/// Most wizard spells of one level a character can know
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpellsPerLevel {
+    Max(i32),
+    /// Every spell of the level can be learned
+    All,
+}
+
+impl Display for SpellsPerLevel {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            SpellsPerLevel::Max(n) => write!(f, "{}", n),
+            SpellsPerLevel::All => write!(f, "All"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Intelligence {
+    pub int: i32,
+    pub err: Option<CharError>,
+}
+
+impl Intelligence {
+    pub fn languages(&self) -> Result<i32> {
+        match self.int {
+            1 => Ok(0),
+            2..=8 => Ok(1),
+            9..=11 => Ok(2),
+            12 | 13 => Ok(3),
+            14 | 15 => Ok(4),
+            16 => Ok(5),
+            17 => Ok(6),
+            18 => Ok(7),
+            19..=23 => Ok(self.int - 11),
+            24 => Ok(15),
+            25 => Ok(20),
+            i => Err(InvalidAbility(Ability::Int, i)),
+        }
+    }
+    /// Highest level of wizard spell that can be cast, if any
+    pub fn max_spell_level(&self) -> Result<Option<i32>> {
+        match self.int {
+            1..=8 => Ok(None),
+            9 => Ok(Some(4)),
+            10 | 11 => Ok(Some(5)),
+            12 | 13 => Ok(Some(6)),
+            14 | 15 => Ok(Some(7)),
+            16 | 17 => Ok(Some(8)),
+            18..=25 => Ok(Some(9)),
+            i => Err(InvalidAbility(Ability::Int, i)),
+        }
+    }
+    /// Percent chance to learn a new wizard spell, if any
+    pub fn chance_to_learn(&self) -> Result<Option<i32>> {
+        match self.int {
+            1..=8 => Ok(None),
+            9..=17 => Ok(Some(5 * self.int - 10)),
+            18 => Ok(Some(85)),
+            19 => Ok(Some(95)),
+            20..=23 => Ok(Some(self.int + 76)),
+            24 | 25 => Ok(Some(100)),
+            i => Err(InvalidAbility(Ability::Int, i)),
+        }
+    }
+    /// Maximum number of wizard spells known per spell level, if the character can learn any
+    pub fn max_spells_per_level(&self) -> Result<Option<SpellsPerLevel>> {
+        match self.int {
+            1..=8 => Ok(None),
+            9 => Ok(Some(SpellsPerLevel::Max(6))),
+            10..=12 => Ok(Some(SpellsPerLevel::Max(7))),
+            13 | 14 => Ok(Some(SpellsPerLevel::Max(9))),
+            15 | 16 => Ok(Some(SpellsPerLevel::Max(11))),
+            17 => Ok(Some(SpellsPerLevel::Max(14))),
+            18 => Ok(Some(SpellsPerLevel::Max(18))),
+            19..=25 => Ok(Some(SpellsPerLevel::All)),
+            i => Err(InvalidAbility(Ability::Int, i)),
+        }
+    }
+    /// Highest level of illusion spells the character is immune to, if any
+    pub fn illusion_immunity(&self) -> Result<Option<i32>> {
+        match self.int {
+            1..=18 => Ok(None),
+            19..=25 => Ok(Some(self.int - 18)),
+            i => Err(InvalidAbility(Ability::Int, i)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Wisdom {
+    pub wis: i32,
+    pub err: Option<CharError>,
+}
+
+impl Wisdom {
+    pub fn magical_defense_adj(&self) -> Result<i32> {
+        match self.wis {
+            1 => Ok(-6),
+            2 => Ok(-4),
+            3 => Ok(-3),
+            4 => Ok(-2),
+            5..=7 => Ok(-1),
+            8..=14 => Ok(0),
+            15 => Ok(1),
+            16 => Ok(2),
+            17 => Ok(3),
+            18..=25 => Ok(4),
+            i => Err(InvalidAbility(Ability::Wis, i)),
+        }
+    }
+    /// Bonus priest spells gained at exactly this score, as spell levels
+    fn bonus_spells_at(wis: i32) -> &'static [usize] {
+        match wis {
+            13 | 14 => &[1],
+            15 | 16 => &[2],
+            17 => &[3],
+            18 => &[4],
+            19 => &[1, 3],
+            20 => &[2, 4],
+            21 => &[3, 5],
+            22 => &[4, 5],
+            23 => &[1, 6],
+            24 => &[5, 6],
+            25 => &[6, 7],
+            _ => &[],
+        }
+    }
+    /// Cumulative bonus priest spells, indexed by spell level - 1
+    pub fn bonus_spells(&self) -> Result<[i32; 7]> {
+        if !(1..=25).contains(&self.wis) {
+            return Err(InvalidAbility(Ability::Wis, self.wis));
+        }
+        let mut bonus = [0; 7];
+        for wis in 13..=self.wis {
+            for level in Wisdom::bonus_spells_at(wis) {
+                bonus[level - 1] += 1;
+            }
+        }
+        Ok(bonus)
+    }
+    pub fn spell_failure(&self) -> Result<i32> {
+        match self.wis {
+            1 => Ok(80),
+            2 => Ok(60),
+            3..=12 => Ok(65 - 5 * self.wis),
+            13..=25 => Ok(0),
+            i => Err(InvalidAbility(Ability::Wis, i)),
+        }
+    }
+    /// Spells gained at exactly this score that the character is immune to
+    fn spell_immunity_at(wis: i32) -> &'static [&'static str] {
+        match wis {
+            19 => &[
+                "cause fear",
+                "charm person",
+                "command",
+                "friends",
+                "hypnotism",
+            ],
+            20 => &["forget", "hold person", "ray of enfeeblement", "scare"],
+            21 => &["fear"],
+            22 => &[
+                "charm monster",
+                "confusion",
+                "emotion",
+                "fumble",
+                "suggestion",
+            ],
+            23 => &["chaos", "feeblemind", "hold monster", "magic jar", "quest"],
+            24 => &["geas", "mass suggestion", "rod of rulership"],
+            25 => &["antipathy/sympathy", "death spell", "mass charm"],
+            _ => &[],
+        }
+    }
+    /// Cumulative list of spells the character is immune to
+    pub fn spell_immunity(&self) -> Result<Vec<&'static str>> {
+        if !(1..=25).contains(&self.wis) {
+            return Err(InvalidAbility(Ability::Wis, self.wis));
+        }
+        Ok((19..=self.wis)
+            .flat_map(|wis| Wisdom::spell_immunity_at(wis).iter().cloned())
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Charisma {
+    pub cha: i32,
+    pub err: Option<CharError>,
+}
+
+impl Charisma {
+    pub fn max_henchmen(&self) -> Result<i32> {
+        match self.cha {
+            1 => Ok(0),
+            2..=4 => Ok(1),
+            5 | 6 => Ok(2),
+            7 | 8 => Ok(3),
+            9..=11 => Ok(4),
+            12 | 13 => Ok(5),
+            14 => Ok(6),
+            15 => Ok(7),
+            16 => Ok(8),
+            17 => Ok(10),
+            18..=25 => Ok(5 * self.cha - 75),
+            i => Err(InvalidAbility(Ability::Cha, i)),
+        }
+    }
+    pub fn loyalty_base(&self) -> Result<i32> {
+        match self.cha {
+            1..=8 => Ok(self.cha - 9),
+            9..=13 => Ok(0),
+            14 => Ok(1),
+            15 => Ok(3),
+            16 => Ok(4),
+            17..=24 => Ok(2 * self.cha - 28),
+            25 => Ok(20),
+            i => Err(InvalidAbility(Ability::Cha, i)),
+        }
+    }
+    pub fn reaction_adj(&self) -> Result<i32> {
+        match self.cha {
+            1..=8 => Ok(self.cha - 8),
+            9..=12 => Ok(0),
+            13 => Ok(1),
+            14 => Ok(2),
+            15 => Ok(3),
+            16..=25 => Ok(self.cha - 11),
+            i => Err(InvalidAbility(Ability::Cha, i)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strength(str: i32, per: Option<i32>) -> StrengthPercentile {
+        StrengthPercentile {
+            str,
+            per,
+            err: None,
+        }
+    }
+
+    #[test]
+    fn open_doors_table() {
+        let rows = vec![
+            (1, None, 1, None),
+            (2, None, 1, None),
+            (3, None, 2, None),
+            (4, None, 3, None),
+            (5, None, 3, None),
+            (6, None, 4, None),
+            (7, None, 4, None),
+            (8, None, 5, None),
+            (9, None, 5, None),
+            (10, None, 6, None),
+            (11, None, 6, None),
+            (12, None, 7, None),
+            (13, None, 7, None),
+            (14, None, 8, None),
+            (15, None, 8, None),
+            (16, None, 9, None),
+            (17, None, 10, None),
+            (18, None, 11, None),
+            (18, Some(1), 12, None),
+            (18, Some(50), 12, None),
+            (18, Some(51), 13, None),
+            (18, Some(75), 13, None),
+            (18, Some(76), 14, None),
+            (18, Some(90), 14, None),
+            (18, Some(91), 15, Some(3)),
+            (18, Some(99), 15, Some(3)),
+            (18, Some(100), 16, Some(6)),
+            (19, None, 16, Some(8)),
+            (20, None, 17, Some(10)),
+            (21, None, 17, Some(12)),
+            (22, None, 18, Some(14)),
+            (23, None, 18, Some(16)),
+            (24, None, 19, Some(17)),
+            (25, None, 19, Some(18)),
+        ];
+        for (str, per, normal, locked) in rows {
+            let od = strength(str, per).open_doors().unwrap();
+            assert_eq!(od, OpenDoors { normal, locked }, "str {} per {:?}", str, per);
+        }
+    }
+
+    #[test]
+    fn open_doors_display() {
+        assert_eq!(strength(17, None).open_doors().unwrap().to_string(), "10");
+        assert_eq!(strength(18, Some(100)).open_doors().unwrap().to_string(), "16(6)");
+        assert_eq!(strength(25, None).open_doors().unwrap().to_string(), "19(18)");
+    }
+
+    #[test]
+    fn bend_bars_table() {
+        let rows = vec![
+            (1, None, 0),
+            (2, None, 0),
+            (3, None, 0),
+            (4, None, 0),
+            (5, None, 0),
+            (6, None, 0),
+            (7, None, 0),
+            (8, None, 1),
+            (9, None, 1),
+            (10, None, 2),
+            (11, None, 2),
+            (12, None, 4),
+            (13, None, 4),
+            (14, None, 7),
+            (15, None, 7),
+            (16, None, 10),
+            (17, None, 13),
+            (18, None, 16),
+            (18, Some(1), 20),
+            (18, Some(50), 20),
+            (18, Some(51), 25),
+            (18, Some(75), 25),
+            (18, Some(76), 30),
+            (18, Some(90), 30),
+            (18, Some(91), 35),
+            (18, Some(99), 35),
+            (18, Some(100), 40),
+            (19, None, 50),
+            (20, None, 60),
+            (21, None, 70),
+            (22, None, 80),
+            (23, None, 90),
+            (24, None, 95),
+            (25, None, 99),
+        ];
+        for (str, per, expected) in rows {
+            assert_eq!(
+                strength(str, per).bend_bars().unwrap(),
+                expected,
+                "str {} per {:?}",
+                str,
+                per
+            );
+        }
+    }
+
+    #[test]
+    fn open_doors_and_bend_bars_out_of_range() {
+        for str in &[0, 26, -1] {
+            match strength(*str, None).open_doors() {
+                Err(CharError::InvalidAbility(Ability::Str, i)) => assert_eq!(i, *str),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+            match strength(*str, None).bend_bars() {
+                Err(CharError::InvalidAbility(Ability::Str, i)) => assert_eq!(i, *str),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+        }
+        for per in &[0, 101] {
+            match strength(18, Some(*per)).open_doors() {
+                Err(CharError::InvalidPercentile(Some(i))) => assert_eq!(i, *per),
+                other => panic!("expected InvalidPercentile, got {:?}", other),
+            }
+            match strength(18, Some(*per)).bend_bars() {
+                Err(CharError::InvalidPercentile(Some(i))) => assert_eq!(i, *per),
+                other => panic!("expected InvalidPercentile, got {:?}", other),
+            }
+        }
+    }
+
+    fn dexterity(dex: i32) -> Dexterity {
+        Dexterity { dex, err: None }
+    }
+
+    #[test]
+    fn dexterity_table() {
+        // (dex, reaction and missile attack adj, defensive adj)
+        let rows = vec![
+            (1, -6, 5),
+            (2, -4, 5),
+            (3, -3, 4),
+            (4, -2, 3),
+            (5, -1, 2),
+            (6, 0, 1),
+            (7, 0, 0),
+            (8, 0, 0),
+            (9, 0, 0),
+            (10, 0, 0),
+            (11, 0, 0),
+            (12, 0, 0),
+            (13, 0, 0),
+            (14, 0, 0),
+            (15, 0, -1),
+            (16, 1, -2),
+            (17, 2, -3),
+            (18, 2, -4),
+            (19, 3, -4),
+            (20, 3, -4),
+            (21, 4, -5),
+            (22, 4, -5),
+            (23, 4, -5),
+            (24, 5, -6),
+            (25, 5, -6),
+        ];
+        for (dex, attack, defensive) in rows {
+            let d = dexterity(dex);
+            assert_eq!(d.reaction_adj().unwrap(), attack, "dex {}", dex);
+            assert_eq!(d.missile_attack_adj().unwrap(), attack, "dex {}", dex);
+            assert_eq!(d.defensive_adj().unwrap(), defensive, "dex {}", dex);
+        }
+    }
+
+    #[test]
+    fn dexterity_out_of_range() {
+        for dex in &[0, 26, -1] {
+            let d = dexterity(*dex);
+            for result in &[d.reaction_adj(), d.missile_attack_adj(), d.defensive_adj()] {
+                match result {
+                    Err(CharError::InvalidAbility(Ability::Dex, i)) => assert_eq!(i, dex),
+                    other => panic!("expected InvalidAbility, got {:?}", other),
+                }
+            }
+        }
+    }
+
+    fn constitution(con: i32, warrior: bool) -> Constitution {
+        Constitution {
+            con,
+            warrior,
+            err: None,
+        }
+    }
+
+    #[test]
+    fn constitution_table() {
+        // (con, hp adj, warrior hp adj, system shock, resurrection, poison save, regeneration)
+        let rows = vec![
+            (1, -3, -3, 25, 30, -2, None),
+            (2, -2, -2, 30, 35, -1, None),
+            (3, -2, -2, 35, 40, 0, None),
+            (4, -1, -1, 40, 45, 0, None),
+            (5, -1, -1, 45, 50, 0, None),
+            (6, -1, -1, 50, 55, 0, None),
+            (7, 0, 0, 55, 60, 0, None),
+            (8, 0, 0, 60, 65, 0, None),
+            (9, 0, 0, 65, 70, 0, None),
+            (10, 0, 0, 70, 75, 0, None),
+            (11, 0, 0, 75, 80, 0, None),
+            (12, 0, 0, 80, 85, 0, None),
+            (13, 0, 0, 85, 90, 0, None),
+            (14, 0, 0, 88, 92, 0, None),
+            (15, 1, 1, 90, 94, 0, None),
+            (16, 2, 2, 95, 96, 0, None),
+            (17, 2, 3, 97, 98, 0, None),
+            (18, 2, 4, 99, 100, 0, None),
+            (19, 2, 5, 99, 100, 1, None),
+            (20, 2, 5, 99, 100, 1, Some(6)),
+            (21, 2, 6, 99, 100, 2, Some(5)),
+            (22, 2, 6, 99, 100, 2, Some(4)),
+            (23, 2, 6, 99, 100, 3, Some(3)),
+            (24, 2, 7, 99, 100, 3, Some(2)),
+            (25, 2, 7, 100, 100, 4, Some(1)),
+        ];
+        for (con, hp, warrior_hp, shock, resurrection, poison, regeneration) in rows {
+            let c = constitution(con, false);
+            assert_eq!(c.hp_adj().unwrap(), hp, "con {}", con);
+            assert_eq!(
+                constitution(con, true).hp_adj().unwrap(),
+                warrior_hp,
+                "warrior con {}",
+                con
+            );
+            assert_eq!(c.system_shock().unwrap(), shock, "con {}", con);
+            assert_eq!(c.resurrection_survival().unwrap(), resurrection, "con {}", con);
+            assert_eq!(c.poison_save().unwrap(), poison, "con {}", con);
+            assert_eq!(c.regeneration().unwrap(), regeneration, "con {}", con);
+        }
+    }
+
+    #[test]
+    fn constitution_out_of_range() {
+        for con in &[0, 26, -1] {
+            for warrior in &[false, true] {
+                match constitution(*con, *warrior).hp_adj() {
+                    Err(CharError::InvalidAbility(Ability::Con, i)) => assert_eq!(i, *con),
+                    other => panic!("expected InvalidAbility, got {:?}", other),
+                }
+            }
+            let c = constitution(*con, false);
+            for result in &[c.system_shock(), c.resurrection_survival(), c.poison_save()] {
+                match result {
+                    Err(CharError::InvalidAbility(Ability::Con, i)) => assert_eq!(i, con),
+                    other => panic!("expected InvalidAbility, got {:?}", other),
+                }
+            }
+            match c.regeneration() {
+                Err(CharError::InvalidAbility(Ability::Con, i)) => assert_eq!(i, *con),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+        }
+    }
+
+    fn intelligence(int: i32) -> Intelligence {
+        Intelligence { int, err: None }
+    }
+
+    #[test]
+    fn intelligence_table() {
+        use SpellsPerLevel::{All, Max};
+        // (int, languages, max spell level, chance to learn, spells per level, illusion immunity)
+        let rows = vec![
+            (1, 0, None, None, None, None),
+            (2, 1, None, None, None, None),
+            (3, 1, None, None, None, None),
+            (4, 1, None, None, None, None),
+            (5, 1, None, None, None, None),
+            (6, 1, None, None, None, None),
+            (7, 1, None, None, None, None),
+            (8, 1, None, None, None, None),
+            (9, 2, Some(4), Some(35), Some(Max(6)), None),
+            (10, 2, Some(5), Some(40), Some(Max(7)), None),
+            (11, 2, Some(5), Some(45), Some(Max(7)), None),
+            (12, 3, Some(6), Some(50), Some(Max(7)), None),
+            (13, 3, Some(6), Some(55), Some(Max(9)), None),
+            (14, 4, Some(7), Some(60), Some(Max(9)), None),
+            (15, 4, Some(7), Some(65), Some(Max(11)), None),
+            (16, 5, Some(8), Some(70), Some(Max(11)), None),
+            (17, 6, Some(8), Some(75), Some(Max(14)), None),
+            (18, 7, Some(9), Some(85), Some(Max(18)), None),
+            (19, 8, Some(9), Some(95), Some(All), Some(1)),
+            (20, 9, Some(9), Some(96), Some(All), Some(2)),
+            (21, 10, Some(9), Some(97), Some(All), Some(3)),
+            (22, 11, Some(9), Some(98), Some(All), Some(4)),
+            (23, 12, Some(9), Some(99), Some(All), Some(5)),
+            (24, 15, Some(9), Some(100), Some(All), Some(6)),
+            (25, 20, Some(9), Some(100), Some(All), Some(7)),
+        ];
+        for (int, languages, level, learn, per_level, illusion) in rows {
+            let i = intelligence(int);
+            assert_eq!(i.languages().unwrap(), languages, "int {}", int);
+            assert_eq!(i.max_spell_level().unwrap(), level, "int {}", int);
+            assert_eq!(i.chance_to_learn().unwrap(), learn, "int {}", int);
+            assert_eq!(i.max_spells_per_level().unwrap(), per_level, "int {}", int);
+            assert_eq!(i.illusion_immunity().unwrap(), illusion, "int {}", int);
+        }
+    }
+
+    #[test]
+    fn intelligence_out_of_range() {
+        for int in &[0, 26, -1] {
+            let i = intelligence(*int);
+            match i.languages() {
+                Err(CharError::InvalidAbility(Ability::Int, n)) => assert_eq!(n, *int),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+            let results = [
+                i.max_spell_level(),
+                i.chance_to_learn(),
+                i.illusion_immunity(),
+            ];
+            for result in &results {
+                match result {
+                    Err(CharError::InvalidAbility(Ability::Int, n)) => assert_eq!(n, int),
+                    other => panic!("expected InvalidAbility, got {:?}", other),
+                }
+            }
+            match i.max_spells_per_level() {
+                Err(CharError::InvalidAbility(Ability::Int, n)) => assert_eq!(n, *int),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+        }
+    }
+
+    fn wisdom(wis: i32) -> Wisdom {
+        Wisdom { wis, err: None }
+    }
+
+    #[test]
+    fn wisdom_table() {
+        // (wis, magical defense adj, spell failure)
+        let rows = vec![
+            (1, -6, 80),
+            (2, -4, 60),
+            (3, -3, 50),
+            (4, -2, 45),
+            (5, -1, 40),
+            (6, -1, 35),
+            (7, -1, 30),
+            (8, 0, 25),
+            (9, 0, 20),
+            (10, 0, 15),
+            (11, 0, 10),
+            (12, 0, 5),
+            (13, 0, 0),
+            (14, 0, 0),
+            (15, 1, 0),
+            (16, 2, 0),
+            (17, 3, 0),
+            (18, 4, 0),
+            (19, 4, 0),
+            (20, 4, 0),
+            (21, 4, 0),
+            (22, 4, 0),
+            (23, 4, 0),
+            (24, 4, 0),
+            (25, 4, 0),
+        ];
+        for (wis, defense, failure) in rows {
+            let w = wisdom(wis);
+            assert_eq!(w.magical_defense_adj().unwrap(), defense, "wis {}", wis);
+            assert_eq!(w.spell_failure().unwrap(), failure, "wis {}", wis);
+        }
+    }
+
+    #[test]
+    fn wisdom_bonus_spells() {
+        assert_eq!(wisdom(12).bonus_spells().unwrap(), [0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(wisdom(13).bonus_spells().unwrap(), [1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(wisdom(17).bonus_spells().unwrap(), [2, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(wisdom(19).bonus_spells().unwrap(), [3, 2, 2, 1, 0, 0, 0]);
+        assert_eq!(wisdom(25).bonus_spells().unwrap(), [4, 3, 3, 3, 3, 3, 1]);
+    }
+
+    #[test]
+    fn wisdom_spell_immunity() {
+        assert!(wisdom(13).spell_immunity().unwrap().is_empty());
+        assert!(wisdom(17).spell_immunity().unwrap().is_empty());
+        assert!(wisdom(18).spell_immunity().unwrap().is_empty());
+        assert_eq!(
+            wisdom(19).spell_immunity().unwrap(),
+            vec![
+                "cause fear",
+                "charm person",
+                "command",
+                "friends",
+                "hypnotism"
+            ]
+        );
+        let all = wisdom(25).spell_immunity().unwrap();
+        assert_eq!(all.len(), 26);
+        assert_eq!(all[..5], wisdom(19).spell_immunity().unwrap()[..]);
+        assert_eq!(
+            all[23..],
+            ["antipathy/sympathy", "death spell", "mass charm"]
+        );
+    }
+
+    #[test]
+    fn wisdom_out_of_range() {
+        for wis in &[0, 26, -1] {
+            let w = wisdom(*wis);
+            for result in &[w.magical_defense_adj(), w.spell_failure()] {
+                match result {
+                    Err(CharError::InvalidAbility(Ability::Wis, i)) => assert_eq!(i, wis),
+                    other => panic!("expected InvalidAbility, got {:?}", other),
+                }
+            }
+            match w.bonus_spells() {
+                Err(CharError::InvalidAbility(Ability::Wis, i)) => assert_eq!(i, *wis),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+            match w.spell_immunity() {
+                Err(CharError::InvalidAbility(Ability::Wis, i)) => assert_eq!(i, *wis),
+                other => panic!("expected InvalidAbility, got {:?}", other),
+            }
+        }
+    }
+
+    fn charisma(cha: i32) -> Charisma {
+        Charisma { cha, err: None }
+    }
+
+    #[test]
+    fn charisma_table() {
+        // (cha, max henchmen, loyalty base, reaction adj)
+        let rows = vec![
+            (1, 0, -8, -7),
+            (2, 1, -7, -6),
+            (3, 1, -6, -5),
+            (4, 1, -5, -4),
+            (5, 2, -4, -3),
+            (6, 2, -3, -2),
+            (7, 3, -2, -1),
+            (8, 3, -1, 0),
+            (9, 4, 0, 0),
+            (10, 4, 0, 0),
+            (11, 4, 0, 0),
+            (12, 5, 0, 0),
+            (13, 5, 0, 1),
+            (14, 6, 1, 2),
+            (15, 7, 3, 3),
+            (16, 8, 4, 5),
+            (17, 10, 6, 6),
+            (18, 15, 8, 7),
+            (19, 20, 10, 8),
+            (20, 25, 12, 9),
+            (21, 30, 14, 10),
+            (22, 35, 16, 11),
+            (23, 40, 18, 12),
+            (24, 45, 20, 13),
+            (25, 50, 20, 14),
+        ];
+        for (cha, henchmen, loyalty, reaction) in rows {
+            let c = charisma(cha);
+            assert_eq!(c.max_henchmen().unwrap(), henchmen, "cha {}", cha);
+            assert_eq!(c.loyalty_base().unwrap(), loyalty, "cha {}", cha);
+            assert_eq!(c.reaction_adj().unwrap(), reaction, "cha {}", cha);
+        }
+    }
+
+    #[test]
+    fn charisma_out_of_range() {
+        for cha in &[0, 26, -1] {
+            let c = charisma(*cha);
+            for result in &[c.max_henchmen(), c.loyalty_base(), c.reaction_adj()] {
+                match result {
+                    Err(CharError::InvalidAbility(Ability::Cha, i)) => assert_eq!(i, cha),
+                    other => panic!("expected InvalidAbility, got {:?}", other),
+                }
+            }
+        }
+    }
+}
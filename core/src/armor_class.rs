@@ -0,0 +1,401 @@
+use crate::common::{CharError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Armor Class of someone wearing nothing at all
+pub const BASE_AC: i32 = 10;
+
+/// Body armor from the Player's Handbook
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ArmorType {
+    #[default]
+    None,
+    Padded,
+    Leather,
+    StuddedLeather,
+    RingMail,
+    Brigandine,
+    ScaleMail,
+    Hide,
+    ChainMail,
+    SplintMail,
+    BandedMail,
+    BronzePlateMail,
+    PlateMail,
+    FieldPlate,
+    FullPlate,
+}
+
+impl FromStr for ArmorType {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ArmorType::all()
+            .into_iter()
+            .find(|armor| armor.to_string() == s)
+            .ok_or_else(|| CharError::ArmorParseError(String::from(s)))
+    }
+}
+
+impl Display for ArmorType {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            ArmorType::None => write!(f, "None"),
+            ArmorType::Padded => write!(f, "Padded"),
+            ArmorType::Leather => write!(f, "Leather"),
+            ArmorType::StuddedLeather => write!(f, "Studded Leather"),
+            ArmorType::RingMail => write!(f, "Ring Mail"),
+            ArmorType::Brigandine => write!(f, "Brigandine"),
+            ArmorType::ScaleMail => write!(f, "Scale Mail"),
+            ArmorType::Hide => write!(f, "Hide"),
+            ArmorType::ChainMail => write!(f, "Chain Mail"),
+            ArmorType::SplintMail => write!(f, "Splint Mail"),
+            ArmorType::BandedMail => write!(f, "Banded Mail"),
+            ArmorType::BronzePlateMail => write!(f, "Bronze Plate Mail"),
+            ArmorType::PlateMail => write!(f, "Plate Mail"),
+            ArmorType::FieldPlate => write!(f, "Field Plate"),
+            ArmorType::FullPlate => write!(f, "Full Plate"),
+        }
+    }
+}
+
+impl ArmorType {
+    /// Every armor, from lightest to heaviest protection
+    pub fn all() -> Vec<ArmorType> {
+        vec![
+            ArmorType::None,
+            ArmorType::Padded,
+            ArmorType::Leather,
+            ArmorType::StuddedLeather,
+            ArmorType::RingMail,
+            ArmorType::Brigandine,
+            ArmorType::ScaleMail,
+            ArmorType::Hide,
+            ArmorType::ChainMail,
+            ArmorType::SplintMail,
+            ArmorType::BandedMail,
+            ArmorType::BronzePlateMail,
+            ArmorType::PlateMail,
+            ArmorType::FieldPlate,
+            ArmorType::FullPlate,
+        ]
+    }
+
+    /// Armor Class the armor gives on its own
+    pub fn base_ac(&self) -> i32 {
+        match self {
+            ArmorType::None => 10,
+            ArmorType::Padded | ArmorType::Leather => 8,
+            ArmorType::StuddedLeather | ArmorType::RingMail => 7,
+            ArmorType::Brigandine | ArmorType::ScaleMail | ArmorType::Hide => 6,
+            ArmorType::ChainMail => 5,
+            ArmorType::SplintMail | ArmorType::BandedMail | ArmorType::BronzePlateMail => 4,
+            ArmorType::PlateMail => 3,
+            ArmorType::FieldPlate => 2,
+            ArmorType::FullPlate => 1,
+        }
+    }
+}
+
+/// Shields from the Player's Handbook
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ShieldType {
+    #[default]
+    None,
+    Buckler,
+    Small,
+    Medium,
+    Body,
+}
+
+impl FromStr for ShieldType {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ShieldType::all()
+            .into_iter()
+            .find(|shield| shield.to_string() == s)
+            .ok_or_else(|| CharError::ArmorParseError(String::from(s)))
+    }
+}
+
+impl Display for ShieldType {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            ShieldType::None => write!(f, "None"),
+            ShieldType::Buckler => write!(f, "Buckler"),
+            ShieldType::Small => write!(f, "Small Shield"),
+            ShieldType::Medium => write!(f, "Medium Shield"),
+            ShieldType::Body => write!(f, "Body Shield"),
+        }
+    }
+}
+
+impl ShieldType {
+    pub fn all() -> Vec<ShieldType> {
+        vec![
+            ShieldType::None,
+            ShieldType::Buckler,
+            ShieldType::Small,
+            ShieldType::Medium,
+            ShieldType::Body,
+        ]
+    }
+
+    /// Every shield improves Armor Class by 1 against the attacks it can block
+    pub fn ac_adj(&self) -> i32 {
+        match self {
+            ShieldType::None => 0,
+            _ => -1,
+        }
+    }
+}
+
+/// What the character wears for protection. Magical bonuses are written as on the item, so
+/// chain mail +1 has an `armor_bonus` of 1.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Protection {
+    pub armor: ArmorType,
+    pub armor_bonus: i32,
+    pub shield: ShieldType,
+    pub shield_bonus: i32,
+    /// Bonus of each ring of protection worn
+    pub rings: Vec<i32>,
+    /// Bonus of the cloak of protection, 0 without one
+    pub cloak: i32,
+}
+
+impl Protection {
+    /// The ring or cloak that protects, if any. Protection devices don't stack with each other
+    /// or with magical armor, so only the best one counts. A cloak also only works over leather
+    /// or no armor at all, and without a shield.
+    pub fn device(&self) -> Option<(&'static str, i32)> {
+        if self.armor != ArmorType::None && self.armor_bonus > 0 {
+            return None;
+        }
+        let ring = self.rings.iter().copied().max().filter(|bonus| *bonus > 0);
+        let cloak_works = matches!(self.armor, ArmorType::None | ArmorType::Leather)
+            && self.shield == ShieldType::None;
+        let cloak = Some(self.cloak).filter(|bonus| cloak_works && *bonus > 0);
+        match (ring, cloak) {
+            (Some(ring), Some(cloak)) if cloak > ring => Some(("Cloak of Protection", cloak)),
+            (Some(ring), _) => Some(("Ring of Protection", ring)),
+            (None, Some(cloak)) => Some(("Cloak of Protection", cloak)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Where an adjustment to Armor Class comes from, which decides when it is lost
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AcSource {
+    Armor,
+    Shield,
+    Dexterity,
+    Device,
+    /// The penalty for carrying a heavy load
+    Encumbrance,
+}
+
+/// Armor Class worked out from everything that adjusts it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmorClass {
+    /// Each adjustment to `BASE_AC` with a label for the sheet. Lower is better.
+    pub adjustments: Vec<(AcSource, String, i32)>,
+}
+
+impl ArmorClass {
+    /// Combines `protection` with the Dexterity defensive adjustment `dex_adj`
+    pub fn new(protection: &Protection, dex_adj: i32) -> ArmorClass {
+        let mut adjustments = vec![];
+        if protection.armor != ArmorType::None {
+            adjustments.push((
+                AcSource::Armor,
+                protection.armor.to_string(),
+                protection.armor.base_ac() - BASE_AC,
+            ));
+            if protection.armor_bonus != 0 {
+                adjustments.push((
+                    AcSource::Armor,
+                    format!("{} {:+}", protection.armor, protection.armor_bonus),
+                    -protection.armor_bonus,
+                ));
+            }
+        }
+        if protection.shield != ShieldType::None {
+            adjustments.push((
+                AcSource::Shield,
+                protection.shield.to_string(),
+                protection.shield.ac_adj(),
+            ));
+            if protection.shield_bonus != 0 {
+                adjustments.push((
+                    AcSource::Shield,
+                    format!("{} {:+}", protection.shield, protection.shield_bonus),
+                    -protection.shield_bonus,
+                ));
+            }
+        }
+        if dex_adj != 0 {
+            adjustments.push((AcSource::Dexterity, "Dex".to_string(), dex_adj));
+        }
+        if let Some((device, bonus)) = protection.device() {
+            adjustments.push((AcSource::Device, format!("{} {:+}", device, bonus), -bonus));
+        }
+        ArmorClass { adjustments }
+    }
+
+    /// Adds an adjustment that isn't part of the protection worn
+    pub fn with(mut self, source: AcSource, label: &str, adj: i32) -> ArmorClass {
+        if adj != 0 {
+            self.adjustments.push((source, label.to_string(), adj));
+        }
+        self
+    }
+
+    fn without<F: Fn(AcSource, i32) -> bool>(&self, lost: F) -> i32 {
+        BASE_AC
+            + self
+                .adjustments
+                .iter()
+                .filter(|(source, _, adj)| !lost(*source, *adj))
+                .map(|(_, _, adj)| adj)
+                .sum::<i32>()
+    }
+
+    pub fn normal(&self) -> i32 {
+        self.without(|_, _| false)
+    }
+
+    pub fn shieldless(&self) -> i32 {
+        self.without(|source, _| source == AcSource::Shield)
+    }
+
+    /// Attacked from behind, the shield and any Dexterity bonus don't help. A Dexterity penalty
+    /// still counts.
+    pub fn rear(&self) -> i32 {
+        self.without(|source, adj| {
+            source == AcSource::Shield || (source == AcSource::Dexterity && adj < 0)
+        })
+    }
+
+    /// Surprised characters lose any Dexterity bonus
+    pub fn surprised(&self) -> i32 {
+        self.without(|source, adj| source == AcSource::Dexterity && adj < 0)
+    }
+}
+
+/// e.g. `AC 3 (Chain Mail -5, Medium Shield -1, Dex -1)`
+impl Display for ArmorClass {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "AC {}", self.normal())?;
+        if !self.adjustments.is_empty() {
+            let adjustments = self
+                .adjustments
+                .iter()
+                .map(|(_, label, adj)| format!("{} {:+}", label, adj))
+                .collect::<Vec<_>>();
+            write!(f, " ({})", adjustments.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a magical bonus, e.g. the 2 of a ring of protection +2
+pub fn parse_bonus(input: &str) -> Result<i32> {
+    if input.is_empty() {
+        return Ok(0);
+    }
+    input
+        .trim_start_matches('+')
+        .parse::<i32>()
+        .map_err(CharError::BonusParseError)
+}
+
+/// Parses a comma separated list of bonuses, one for each ring worn
+pub fn parse_bonuses(input: &str) -> Result<Vec<i32>> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|bonus| !bonus.is_empty())
+        .map(parse_bonus)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armor_shield_and_dexterity() {
+        for armor in ArmorType::all() {
+            assert_eq!(armor.to_string().parse::<ArmorType>().unwrap(), armor);
+        }
+        for shield in ShieldType::all() {
+            assert_eq!(shield.to_string().parse::<ShieldType>().unwrap(), shield);
+        }
+        let protection = Protection {
+            armor: ArmorType::ChainMail,
+            armor_bonus: 1,
+            shield: ShieldType::Medium,
+            ..Default::default()
+        };
+        let ac = ArmorClass::new(&protection, -2);
+        assert_eq!(ac.normal(), 1);
+        assert_eq!(ac.shieldless(), 2);
+        assert_eq!(ac.rear(), 4);
+        assert_eq!(ac.surprised(), 3);
+        assert_eq!(
+            ac.to_string(),
+            "AC 1 (Chain Mail -5, Chain Mail +1 -1, Medium Shield -1, Dex -2)"
+        );
+
+        // a new sheet wears nothing, and a Dexterity penalty isn't lost
+        assert_eq!(Protection::default().armor, ArmorType::None);
+        assert_eq!(Protection::default().shield, ShieldType::None);
+        let clumsy = ArmorClass::new(&Protection::default(), 2);
+        assert_eq!((clumsy.normal(), clumsy.rear(), clumsy.surprised()), (12, 12, 12));
+    }
+
+    #[test]
+    fn protection_devices_dont_stack() {
+        let mut protection = Protection {
+            rings: vec![1, 2],
+            cloak: 3,
+            ..Default::default()
+        };
+        assert_eq!(protection.device(), Some(("Cloak of Protection", 3)));
+        protection.shield = ShieldType::Small;
+        assert_eq!(protection.device(), Some(("Ring of Protection", 2)));
+        protection.armor = ArmorType::PlateMail;
+        assert_eq!(protection.device(), Some(("Ring of Protection", 2)));
+        protection.armor_bonus = 1;
+        assert_eq!(protection.device(), None);
+    }
+
+    #[test]
+    fn armor_bonus_needs_armor() {
+        let protection = Protection {
+            armor_bonus: 1,
+            rings: vec![2],
+            ..Default::default()
+        };
+        assert_eq!(protection.device(), Some(("Ring of Protection", 2)));
+        let ac = ArmorClass::new(&protection, 0);
+        assert_eq!(ac.normal(), 8);
+        assert_eq!(ac.to_string(), "AC 8 (Ring of Protection +2 -2)");
+    }
+
+    #[test]
+    fn bonuses() {
+        assert_eq!(parse_bonus("+2").unwrap(), 2);
+        assert_eq!(parse_bonus("").unwrap(), 0);
+        assert_eq!(parse_bonuses("1, +3").unwrap(), vec![1, 3]);
+        match parse_bonuses("1, lots") {
+            Err(CharError::BonusParseError(_)) => (),
+            other => panic!("expected BonusParseError, got {:?}", other),
+        }
+    }
+}
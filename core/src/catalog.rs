@@ -0,0 +1,233 @@
+use crate::armor_class::{ArmorType, ShieldType};
+
+/// How a missile weapon is used, which decides how often it can be fired
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Missile {
+    Bow,
+    Crossbow,
+    Dart,
+    /// Slings and thrown weapons other than darts
+    Other,
+    /// Arrows, quarrels and bullets, which are fired from another weapon rather than used alone
+    Ammunition,
+}
+
+/// What kind of thing a catalog item is, with the rules that go with it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Weapon {
+        /// Damage against small and man-sized creatures
+        damage_sm: &'static str,
+        /// Damage against large creatures
+        damage_l: &'static str,
+        /// How the weapon is fired or thrown, `None` for weapons swung in melee
+        missile: Option<Missile>,
+    },
+    Armor(ArmorType),
+    Shield(ShieldType),
+    Gear,
+}
+
+/// An item from the Player's Handbook equipment lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CatalogItem {
+    pub name: &'static str,
+    pub kind: ItemKind,
+    /// Cost in copper pieces
+    pub cost: u32,
+    /// Weight in pounds
+    pub weight: f32,
+}
+
+impl CatalogItem {
+    /// How a weapon is fired or thrown, `None` for melee weapons and anything that isn't a weapon
+    pub fn missile(&self) -> Option<Missile> {
+        match self.kind {
+            ItemKind::Weapon { missile, .. } => missile,
+            _ => None,
+        }
+    }
+}
+
+/// Copper pieces in a gold piece, for writing down catalog prices
+const GP: u32 = 100;
+/// Copper pieces in a silver piece
+const SP: u32 = 10;
+
+const fn weapon(
+    name: &'static str,
+    cost: u32,
+    weight: f32,
+    damage_sm: &'static str,
+    damage_l: &'static str,
+    missile: Option<Missile>,
+) -> CatalogItem {
+    CatalogItem {
+        name,
+        kind: ItemKind::Weapon {
+            damage_sm,
+            damage_l,
+            missile,
+        },
+        cost,
+        weight,
+    }
+}
+
+const fn armor(name: &'static str, armor: ArmorType, cost: u32, weight: f32) -> CatalogItem {
+    CatalogItem {
+        name,
+        kind: ItemKind::Armor(armor),
+        cost,
+        weight,
+    }
+}
+
+const fn shield(name: &'static str, shield: ShieldType, cost: u32, weight: f32) -> CatalogItem {
+    CatalogItem {
+        name,
+        kind: ItemKind::Shield(shield),
+        cost,
+        weight,
+    }
+}
+
+const fn gear(name: &'static str, cost: u32, weight: f32) -> CatalogItem {
+    CatalogItem {
+        name,
+        kind: ItemKind::Gear,
+        cost,
+        weight,
+    }
+}
+
+/// Everything that can be bought, weapons first, then armor, shields and gear
+pub const CATALOG: &[CatalogItem] = &[
+    weapon("Battle Axe", 5 * GP, 7.0, "1d8", "1d8", None),
+    weapon("Hand Axe", GP, 5.0, "1d6", "1d4", None),
+    weapon("Club", 0, 3.0, "1d6", "1d3", None),
+    weapon("Dagger", 2 * GP, 1.0, "1d4", "1d3", None),
+    weapon("Footman's Flail", 15 * GP, 15.0, "1d6+1", "2d4", None),
+    weapon("Footman's Mace", 8 * GP, 10.0, "1d6+1", "1d6", None),
+    weapon("Morning Star", 10 * GP, 12.0, "2d4", "1d6+1", None),
+    weapon("Halberd", 10 * GP, 15.0, "1d10", "2d6", None),
+    weapon("Quarterstaff", 0, 4.0, "1d6", "1d6", None),
+    weapon("Spear", 8 * SP, 5.0, "1d6", "1d8", None),
+    weapon("Bastard Sword", 25 * GP, 10.0, "1d8", "1d12", None),
+    weapon("Broad Sword", 10 * GP, 4.0, "2d4", "1d6+1", None),
+    weapon("Long Sword", 15 * GP, 4.0, "1d8", "1d12", None),
+    weapon("Scimitar", 15 * GP, 4.0, "1d8", "1d8", None),
+    weapon("Short Sword", 10 * GP, 3.0, "1d6", "1d8", None),
+    weapon("Two-Handed Sword", 50 * GP, 15.0, "1d10", "3d6", None),
+    weapon("Warhammer", 2 * GP, 6.0, "1d4+1", "1d4", None),
+    weapon("Dart", 5 * SP, 0.5, "1d3", "1d2", Some(Missile::Dart)),
+    weapon("Javelin", 5 * SP, 2.0, "1d6", "1d6", Some(Missile::Other)),
+    weapon("Short Bow", 30 * GP, 2.0, "-", "-", Some(Missile::Bow)),
+    weapon("Long Bow", 75 * GP, 3.0, "-", "-", Some(Missile::Bow)),
+    weapon("Flight Arrow", 5, 0.1, "1d6", "1d6", Some(Missile::Ammunition)),
+    weapon("Sheaf Arrow", 5, 0.1, "1d8", "1d8", Some(Missile::Ammunition)),
+    weapon("Light Crossbow", 35 * GP, 7.0, "-", "-", Some(Missile::Crossbow)),
+    weapon("Light Quarrel", SP, 0.1, "1d4", "1d4", Some(Missile::Ammunition)),
+    weapon("Sling", 5, 0.0, "-", "-", Some(Missile::Other)),
+    weapon("Sling Bullet", 1, 0.5, "1d4+1", "1d6+1", Some(Missile::Ammunition)),
+    armor("Padded", ArmorType::Padded, 4 * GP, 10.0),
+    armor("Leather", ArmorType::Leather, 5 * GP, 15.0),
+    armor("Studded Leather", ArmorType::StuddedLeather, 20 * GP, 25.0),
+    armor("Ring Mail", ArmorType::RingMail, 100 * GP, 30.0),
+    armor("Brigandine", ArmorType::Brigandine, 120 * GP, 35.0),
+    armor("Scale Mail", ArmorType::ScaleMail, 120 * GP, 40.0),
+    armor("Hide", ArmorType::Hide, 15 * GP, 30.0),
+    armor("Chain Mail", ArmorType::ChainMail, 75 * GP, 40.0),
+    armor("Splint Mail", ArmorType::SplintMail, 80 * GP, 40.0),
+    armor("Banded Mail", ArmorType::BandedMail, 200 * GP, 35.0),
+    armor("Bronze Plate Mail", ArmorType::BronzePlateMail, 400 * GP, 45.0),
+    armor("Plate Mail", ArmorType::PlateMail, 600 * GP, 50.0),
+    armor("Field Plate", ArmorType::FieldPlate, 2_000 * GP, 60.0),
+    armor("Full Plate", ArmorType::FullPlate, 4_000 * GP, 70.0),
+    shield("Buckler", ShieldType::Buckler, GP, 3.0),
+    shield("Small Shield", ShieldType::Small, 3 * GP, 5.0),
+    shield("Medium Shield", ShieldType::Medium, 7 * GP, 10.0),
+    shield("Body Shield", ShieldType::Body, 10 * GP, 15.0),
+    gear("Backpack", 2 * GP, 2.0),
+    gear("Belt Pouch, Large", GP, 1.0),
+    gear("Belt Pouch, Small", 7 * SP, 0.5),
+    gear("Blanket, Winter", 5 * SP, 3.0),
+    gear("Candle", 1, 0.0),
+    gear("Chalk", 1, 0.0),
+    gear("Flint and Steel", 5 * SP, 0.0),
+    gear("Grappling Hook", 8 * SP, 4.0),
+    gear("Holy Symbol, Silver", 25 * GP, 1.0),
+    gear("Holy Symbol, Wooden", 7 * SP, 0.0),
+    gear("Lantern, Hooded", 7 * GP, 2.0),
+    gear("Mirror, Small Metal", 10 * GP, 0.0),
+    gear("Oil, Flask", 6, 1.0),
+    gear("Rations, Iron (1 week)", 5 * GP, 15.0),
+    gear("Rations, Standard (1 week)", 3 * GP, 20.0),
+    gear("Rope, Hemp (50')", GP, 20.0),
+    gear("Rope, Silk (50')", 10 * GP, 8.0),
+    gear("Sack, Large", 2 * SP, 0.5),
+    gear("Spell Book", 25 * GP, 3.0),
+    gear("Thieves' Picks", 30 * GP, 1.0),
+    gear("Torch", 1, 1.0),
+    gear("Waterskin", 8 * SP, 1.0),
+];
+
+/// Writes a price in copper pieces the way the catalog lists them, e.g. `15 gp 5 sp`
+pub fn format_cost(cost: u32) -> String {
+    let parts = [(cost / GP, "gp"), (cost % GP / SP, "sp"), (cost % SP, "cp")]
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, coin)| format!("{} {}", amount, coin))
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        "0 cp".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Looks up a catalog item by its exact name
+pub fn find(name: &str) -> Option<&'static CatalogItem> {
+    CATALOG.iter().find(|item| item.name == name)
+}
+
+/// Looks up a weapon that can be attacked with by its exact name, leaving out ammunition
+pub fn find_weapon(name: &str) -> Option<&'static CatalogItem> {
+    find(name).filter(|item| match item.kind {
+        ItemKind::Weapon { missile, .. } => missile != Some(Missile::Ammunition),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_names_are_unique() {
+        for (i, item) in CATALOG.iter().enumerate() {
+            assert!(
+                CATALOG[i + 1..].iter().all(|other| other.name != item.name),
+                "{} is listed twice",
+                item.name
+            );
+        }
+        assert_eq!(find("Long Sword").unwrap().cost, 1_500);
+        assert_eq!(
+            find("Chain Mail").unwrap().kind,
+            ItemKind::Armor(ArmorType::ChainMail)
+        );
+        assert_eq!(find("Vorpal Sword"), None);
+        assert_eq!(find_weapon("Long Bow").unwrap().missile(), Some(Missile::Bow));
+        assert_eq!(find_weapon("Flight Arrow"), None);
+        assert_eq!(find_weapon("Torch"), None);
+    }
+
+    #[test]
+    fn costs() {
+        assert_eq!(format_cost(1_500), "15 gp");
+        assert_eq!(format_cost(1_553), "15 gp 5 sp 3 cp");
+        assert_eq!(format_cost(6), "6 cp");
+        assert_eq!(format_cost(0), "0 cp");
+    }
+}
@@ -0,0 +1,980 @@
+use crate::abilities::{
+    Charisma, Constitution, Dexterity, Intelligence, StrengthPercentile, Wisdom,
+};
+use crate::armor_class::{AcSource, ArmorClass, ArmorType, Protection, ShieldType};
+use crate::catalog::{self, ItemKind};
+use crate::class::CharacterClass;
+use crate::combat::{Attack, AttackRate, WeaponAttack, WeaponSkill};
+use crate::common::{Ability, CharError, Result};
+use crate::dice::Rng;
+use crate::encumbrance::Encumbrance;
+use crate::generation::GenerationMethod;
+use crate::hit_points::HitPoints;
+use crate::inventory::{Inventory, Item, Location};
+use crate::level::{self, LevelUp};
+use crate::proficiency::Proficiencies;
+use crate::purse::Purse;
+use crate::race::Race;
+use crate::save_format;
+use crate::saves::{self, SaveCategory, SavingThrow};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Character {
+    /// Id the character is saved under in the roster, if it has been saved
+    #[serde(skip)]
+    pub save_id: Option<u32>,
+    pub char_name: String,
+    pub char_class: CharacterClass,
+    pub race: Race,
+    pub str: i32,
+    pub str_percentile: Option<i32>,
+    pub dex: i32,
+    pub con: i32,
+    pub int: i32,
+    pub wis: i32,
+    pub cha: i32,
+    /// How the ability scores were rolled, if they were generated rather than typed in
+    pub generation_method: Option<GenerationMethod>,
+    pub hit_points: HitPoints,
+    /// Experience points, the level is worked out from these
+    pub xp: u32,
+    /// Armor, shield and protective magic worn
+    pub protection: Protection,
+    pub inventory: Inventory,
+    /// Coins, gems and jewelry, and what was bought with them
+    pub purse: Purse,
+    /// Weapons learned with proficiency slots
+    pub proficiencies: Proficiencies,
+}
+
+impl Character {
+    /// Reads a character from JSON, upgrading documents saved in older formats first. Documents
+    /// that can't be read are rejected with the path to the offending field. Documents that break
+    /// the character rules are still loaded, the same way the form keeps invalid scores, and the
+    /// broken rules are returned alongside.
+    pub fn from_json_str(
+        json: &str,
+    ) -> std::result::Result<(Character, Vec<CharError>), Vec<CharError>> {
+        let document: serde_json::Value = serde_json::from_str(json).map_err(|e| {
+            vec![CharError::ImportError {
+                path: ".".to_string(),
+                reason: e.to_string(),
+            }]
+        })?;
+        let upgraded = save_format::upgrade(document).map_err(|e| vec![e])?;
+        let character: Character = serde_path_to_error::deserialize(upgraded).map_err(|e| {
+            vec![CharError::ImportError {
+                path: e.path().to_string(),
+                reason: e.inner().to_string(),
+            }]
+        })?;
+        let errors = character.validate();
+        Ok((character, errors))
+    }
+
+    /// Runs every rule the form checks, tagging each broken rule with the field it belongs to
+    pub fn validate(&self) -> Vec<CharError> {
+        let mut errors = vec![];
+        for ability in Ability::all() {
+            // scores that were never entered aren't errors in the form either
+            if self.ability(ability) == 0 {
+                continue;
+            }
+            if let Err(e) = self.validate_ability(ability) {
+                errors.push(CharError::ImportError {
+                    path: ability.field_name().to_string(),
+                    reason: e.to_string(),
+                });
+            }
+        }
+        if let Some(per) = self.str_percentile {
+            if !(1..=100).contains(&per) {
+                errors.push(CharError::ImportError {
+                    path: "str_percentile".to_string(),
+                    reason: CharError::InvalidPercentile(Some(per)).to_string(),
+                });
+            }
+        }
+        if let Err(e) = self.validate_class() {
+            errors.push(CharError::ImportError {
+                path: "char_class".to_string(),
+                reason: e.to_string(),
+            });
+        }
+        errors
+    }
+
+    pub fn ability(&self, ability: Ability) -> i32 {
+        match ability {
+            Ability::Str => self.str,
+            Ability::Dex => self.dex,
+            Ability::Con => self.con,
+            Ability::Int => self.int,
+            Ability::Wis => self.wis,
+            Ability::Cha => self.cha,
+        }
+    }
+
+    pub fn ability_mut(&mut self, ability: Ability) -> &mut i32 {
+        match ability {
+            Ability::Str => &mut self.str,
+            Ability::Dex => &mut self.dex,
+            Ability::Con => &mut self.con,
+            Ability::Int => &mut self.int,
+            Ability::Wis => &mut self.wis,
+            Ability::Cha => &mut self.cha,
+        }
+    }
+
+    /// The score after racial adjustments, which is what the ability tables are looked up with.
+    /// Scores that haven't been entered yet stay at 0.
+    pub fn effective_ability(&self, ability: Ability) -> i32 {
+        match self.ability(ability) {
+            0 => 0,
+            val => (val + self.race.ability_adjustment(ability)).clamp(1, 25),
+        }
+    }
+
+    /// Warriors with 18 Strength roll percentile dice for exceptional Strength. Anyone else has no
+    /// percentile score.
+    pub fn roll_exceptional_strength<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.str_percentile =
+            if self.char_class.is_warrior() && self.effective_ability(Ability::Str) == 18 {
+                Some(rng.roll_die(100) as i32)
+            } else {
+                None
+            };
+    }
+
+    /// Constitution hit point adjustment per level, 0 until Constitution has been entered
+    pub fn con_hp_adj(&self) -> i32 {
+        self.constitution().hp_adj().unwrap_or(0)
+    }
+
+    pub fn max_hp(&self) -> i32 {
+        self.hit_points.max(self.char_class.group(), self.con_hp_adj())
+    }
+
+    pub fn current_hp(&self) -> i32 {
+        self.hit_points.current(self.char_class.group(), self.con_hp_adj())
+    }
+
+    /// Adds the hit points for the next level
+    pub fn roll_hit_points<R: Rng + ?Sized>(&mut self, rng: &mut R) -> i32 {
+        self.hit_points.roll_level(self.char_class.group(), rng)
+    }
+
+    pub fn level(&self) -> u32 {
+        level::level_for_xp(self.char_class, self.xp)
+    }
+
+    /// Experience points needed for the next level, `None` at the top of the tables
+    pub fn next_level_xp(&self) -> Option<u32> {
+        level::xp_for_level(self.char_class, self.level() + 1)
+    }
+
+    /// Whether every prime requisite is 16 or more, earning 10% extra experience
+    pub fn xp_bonus(&self) -> bool {
+        self.char_class
+            .prime_requisites()
+            .into_iter()
+            .all(|ability| self.effective_ability(ability) >= 16)
+    }
+
+    /// Levels that haven't had their hit points rolled yet
+    pub fn hit_dice_owed(&self) -> usize {
+        (self.level() as usize).saturating_sub(self.hit_points.rolls.len())
+    }
+
+    /// Sets the experience total, returning what changed if that gained a level
+    pub fn set_xp(&mut self, xp: u32) -> Option<LevelUp> {
+        let from = self.level();
+        self.xp = xp;
+        LevelUp::new(self.char_class, from, self.level(), self.hit_points.rolls.len())
+    }
+
+    /// Awards experience, adding the prime requisite bonus if the character has it
+    pub fn award_xp(&mut self, xp: u32) -> Option<LevelUp> {
+        let bonus = if self.xp_bonus() { xp / 10 } else { 0 };
+        self.set_xp(self.xp.saturating_add(xp).saturating_add(bonus))
+    }
+
+    pub fn thac0(&self) -> i32 {
+        self.char_class.group().thac0(self.level())
+    }
+
+    /// Attacks in melee get the Strength adjustments to hit and to damage
+    pub fn melee_attack(&self, skill: WeaponSkill) -> Attack {
+        let str = self.strength();
+        let attack = Attack::new(self.thac0()).with(
+            "Str",
+            str.hit_adj().unwrap_or(0),
+            str.damage_adj().unwrap_or(0),
+        );
+        self.with_weapon_skill(attack, skill)
+    }
+
+    /// Missile attacks get the Dexterity adjustment to hit
+    pub fn missile_attack(&self, skill: WeaponSkill) -> Attack {
+        let dex = self.dexterity().missile_attack_adj().unwrap_or(0);
+        let attack = Attack::new(self.thac0()).with("Dex", dex, 0);
+        self.with_weapon_skill(attack, skill)
+    }
+
+    fn with_weapon_skill(&self, attack: Attack, skill: WeaponSkill) -> Attack {
+        attack
+            .with(
+                &skill.to_string(),
+                skill.hit_adj(self.char_class.group()),
+                skill.damage_adj(),
+            )
+            .with("Encumbrance", self.encumbrance().hit_adj(), 0)
+    }
+
+    pub fn weapon_slots(&self) -> u32 {
+        self.char_class.group().weapon_slots(self.level())
+    }
+
+    /// Weapon proficiency slots spent. A specialization set aside by a class change doesn't
+    /// take any.
+    pub fn weapon_slots_used(&self) -> u32 {
+        let used = self.proficiencies.slots_used();
+        match &self.proficiencies.specialization {
+            Some(s) if self.char_class != CharacterClass::Fighter => used - s.slots(),
+            _ => used,
+        }
+    }
+
+    /// Weapon proficiency slots not spent yet
+    pub fn weapon_slots_left(&self) -> u32 {
+        self.weapon_slots().saturating_sub(self.weapon_slots_used())
+    }
+
+    fn check_slots(&self, needed: u32) -> Result<()> {
+        let left = self.weapon_slots_left();
+        if needed > left {
+            return Err(CharError::NoProficiencySlots { needed, left });
+        }
+        Ok(())
+    }
+
+    /// Spends slots on a weapon, or a whole weapon group by its name
+    pub fn learn_weapon(&mut self, name: &str) -> Result<()> {
+        self.check_slots(self.proficiencies.slots_for(name)?)?;
+        self.proficiencies.learn(name)
+    }
+
+    pub fn forget_weapon(&mut self, name: &str) {
+        self.proficiencies.forget(name)
+    }
+
+    /// Specializes in a weapon the character is proficient with, or double specializes in the
+    /// melee weapon already specialized in. Only fighters can specialize.
+    pub fn specialize(&mut self, weapon: &str) -> Result<()> {
+        if self.char_class != CharacterClass::Fighter {
+            return Err(CharError::CantSpecialize(format!(
+                "{}s can't specialize",
+                self.char_class
+            )));
+        }
+        let next = self.proficiencies.next_specialization(weapon)?;
+        let spent = self.proficiencies.specialization.as_ref().map_or(0, |s| s.slots());
+        self.check_slots(next.slots() - spent)?;
+        self.proficiencies.specialization = Some(next);
+        Ok(())
+    }
+
+    /// How well the character knows `weapon`. A specialization only counts for fighters, so it
+    /// is set aside if the class changes.
+    pub fn weapon_skill(&self, weapon: &str) -> WeaponSkill {
+        match self.proficiencies.skill(weapon) {
+            skill if skill.is_specialized() && self.char_class != CharacterClass::Fighter => {
+                WeaponSkill::Proficient
+            }
+            skill => skill,
+        }
+    }
+
+    /// Attacking with a catalog weapon, in melee or as a missile depending on the weapon
+    pub fn weapon_attack(&self, weapon: &str) -> Result<WeaponAttack> {
+        let item =
+            catalog::find_weapon(weapon).ok_or_else(|| CharError::NotAWeapon(weapon.to_string()))?;
+        let skill = self.weapon_skill(weapon);
+        let attack = match item.missile() {
+            Some(_) => self.missile_attack(skill),
+            None => self.melee_attack(skill),
+        };
+        let rate = AttackRate::new(
+            self.char_class.group(),
+            self.level(),
+            item.missile(),
+            skill,
+        );
+        Ok(WeaponAttack {
+            weapon: item.name.to_string(),
+            skill,
+            attack,
+            rate,
+        })
+    }
+
+    /// Attacks with every weapon the character has to hand, then with the other weapons they
+    /// have learned
+    pub fn weapon_attacks(&self) -> Vec<WeaponAttack> {
+        let mut weapons: Vec<&str> = vec![];
+        let on_hand = self
+            .inventory
+            .items
+            .iter()
+            .filter(|item| item.location != Location::Mount)
+            .map(|item| item.name.as_str());
+        for weapon in on_hand.chain(self.proficiencies.weapons.iter().map(|w| w.as_str())) {
+            if !weapons.contains(&weapon) {
+                weapons.push(weapon);
+            }
+        }
+        weapons
+            .into_iter()
+            .filter_map(|weapon| self.weapon_attack(weapon).ok())
+            .collect()
+    }
+
+    pub fn armor_class(&self) -> ArmorClass {
+        let dex_adj = self.dexterity().defensive_adj().unwrap_or(0);
+        ArmorClass::new(&self.protection, dex_adj).with(
+            AcSource::Encumbrance,
+            "Encumbrance",
+            self.encumbrance().ac_adj(),
+        )
+    }
+
+    /// Pounds the character is carrying, coins included, leaving out whatever is on the mount
+    pub fn load(&self) -> f32 {
+        self.inventory.carried_weight() + self.purse.weight()
+    }
+
+    /// How encumbered the load makes the character. Without a valid Strength there's nothing to
+    /// compare the load with, so the character counts as unencumbered.
+    pub fn encumbrance(&self) -> Encumbrance {
+        Encumbrance::for_load(&self.strength(), self.load()).unwrap_or(Encumbrance::Unencumbered)
+    }
+
+    /// Movement rate with the load carried
+    pub fn movement(&self) -> i32 {
+        self.encumbrance().movement(self.race.base_movement())
+    }
+
+    /// Adds catalog items to the inventory, returning the index of their stack
+    pub fn add_item(&mut self, name: &str, quantity: u32, location: Location) -> Result<usize> {
+        let index = self.inventory.add(name, quantity, location)?;
+        self.wear_protection(&self.inventory.items[index].clone());
+        Ok(index)
+    }
+
+    /// Pays for catalog items from the purse and adds them to the inventory, returning the index
+    /// of their stack. If the items can't be added the purse is left as it was.
+    pub fn buy_item(&mut self, name: &str, quantity: u32, location: Location) -> Result<usize> {
+        let item = catalog::find(name).ok_or_else(|| CharError::UnknownItem(name.to_string()))?;
+        if quantity == 0 {
+            return Err(CharError::InvalidQuantity(quantity));
+        }
+        let before = self.purse.clone();
+        self.purse.buy(item, quantity)?;
+        match self.add_item(name, quantity, location) {
+            Ok(index) => Ok(index),
+            Err(e) => {
+                self.purse = before;
+                Err(e)
+            }
+        }
+    }
+
+    pub fn remove_item(&mut self, index: usize, quantity: Option<u32>) -> Result<Item> {
+        let removed = self.inventory.remove(index, quantity)?;
+        self.wear_protection(&removed);
+        Ok(removed)
+    }
+
+    pub fn move_item(&mut self, index: usize, location: Location) -> Result<()> {
+        let item = self.inventory.items.get(index).cloned();
+        self.inventory.move_to(index, location)?;
+        if let Some(item) = item {
+            self.wear_protection(&item);
+        }
+        Ok(())
+    }
+
+    /// When armor or a shield comes or goes, the protection follows whatever armor and shield
+    /// are worn now. Magical bonuses are left for the player to set.
+    fn wear_protection(&mut self, changed: &Item) {
+        let worn = |kind: fn(ItemKind) -> bool| {
+            self.inventory
+                .at(Location::Worn)
+                .filter_map(|item| item.catalog_item())
+                .map(|item| item.kind)
+                .find(|k| kind(*k))
+        };
+        match changed.catalog_item().map(|item| item.kind) {
+            Some(ItemKind::Armor(_)) => {
+                self.protection.armor = match worn(|k| matches!(k, ItemKind::Armor(_))) {
+                    Some(ItemKind::Armor(armor)) => armor,
+                    _ => ArmorType::None,
+                }
+            }
+            Some(ItemKind::Shield(_)) => {
+                self.protection.shield = match worn(|k| matches!(k, ItemKind::Shield(_))) {
+                    Some(ItemKind::Shield(shield)) => shield,
+                    _ => ShieldType::None,
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Saving throws for the class and level, with the Constitution bonuses of the hardy races and
+    /// the Wisdom adjustment against spells that affect the mind
+    pub fn saving_throws(&self) -> Vec<SavingThrow> {
+        let base = saves::base_saves(self.char_class.group(), self.level());
+        let (magic, poison) = self.race.save_bonus(self.effective_ability(Ability::Con));
+        let mind = self.wisdom().magical_defense_adj().unwrap_or(0);
+        SaveCategory::all()
+            .into_iter()
+            .zip(base.iter())
+            .map(|(category, target)| {
+                let mut save = SavingThrow {
+                    category,
+                    target: *target,
+                    situational: vec![],
+                };
+                match category {
+                    SaveCategory::ParalyzationPoisonDeath if poison != 0 => {
+                        save.situational.push(("poison", poison))
+                    }
+                    SaveCategory::RodStaffWand => save.target -= magic,
+                    SaveCategory::Spell => {
+                        save.target -= magic;
+                        if mind != 0 {
+                            save.situational.push(("mind-affecting spells", mind));
+                        }
+                    }
+                    _ => (),
+                }
+                save
+            })
+            .collect()
+    }
+
+    /// The Strength table for the adjusted score
+    pub fn strength(&self) -> StrengthPercentile {
+        StrengthPercentile {
+            str: self.effective_ability(Ability::Str),
+            per: self.str_percentile,
+            err: None,
+        }
+    }
+
+    pub fn dexterity(&self) -> Dexterity {
+        Dexterity {
+            dex: self.effective_ability(Ability::Dex),
+            err: None,
+        }
+    }
+
+    pub fn constitution(&self) -> Constitution {
+        Constitution {
+            con: self.effective_ability(Ability::Con),
+            warrior: self.char_class.is_warrior(),
+            err: None,
+        }
+    }
+
+    pub fn intelligence(&self) -> Intelligence {
+        Intelligence {
+            int: self.effective_ability(Ability::Int),
+            err: None,
+        }
+    }
+
+    pub fn wisdom(&self) -> Wisdom {
+        Wisdom {
+            wis: self.effective_ability(Ability::Wis),
+            err: None,
+        }
+    }
+
+    pub fn charisma(&self) -> Charisma {
+        Charisma {
+            cha: self.effective_ability(Ability::Cha),
+            err: None,
+        }
+    }
+
+    /// Checks the stored score against the valid range, the racial limits and the class
+    /// requirements. Racial limits apply to the rolled score, class requirements to the adjusted
+    /// one.
+    pub fn validate_ability(&self, ability: Ability) -> Result<i32> {
+        let val = self.ability(ability);
+        if !(1..=25).contains(&val) {
+            return Err(CharError::InvalidAbility(ability, val));
+        }
+        let (race_min, race_max) = self.race.ability_limits(ability);
+        if val < race_min {
+            return Err(CharError::RacialMinimum {
+                ability,
+                min: race_min,
+                race: self.race,
+            });
+        }
+        if val > race_max {
+            return Err(CharError::RacialMaximum {
+                ability,
+                max: race_max,
+                race: self.race,
+            });
+        }
+        let val = self.effective_ability(ability);
+        if let Some(min) = self.char_class.ability_minimum(ability) {
+            if val < min {
+                return Err(CharError::ClassMinimum {
+                    ability,
+                    min,
+                    class: self.char_class,
+                });
+            }
+        }
+        Ok(val)
+    }
+
+    /// Parses and stores an ability score. In range scores are kept even if they fail the class
+    /// requirements so the derived values still show up.
+    pub fn handle_ability_update(&mut self, ability: Ability, input: &str) -> Result<i32> {
+        let val = input
+            .parse::<i32>()
+            .map_err(|e| CharError::AbilityParseError(ability, e))?;
+        if !(1..=25).contains(&val) {
+            return Err(CharError::InvalidAbility(ability, val));
+        }
+        *self.ability_mut(ability) = val;
+        self.validate_ability(ability)
+    }
+
+    pub fn validate_class(&self) -> Result<CharacterClass> {
+        if self.race.allows_class(self.char_class) {
+            Ok(self.char_class)
+        } else {
+            Err(CharError::ClassNotAllowed {
+                race: self.race,
+                class: self.char_class,
+            })
+        }
+    }
+
+    pub fn handle_str_percentile_update(&mut self, input: &str) -> Result<Option<i32>> {
+        if input.is_empty() {
+            self.str_percentile = None;
+            return Ok(None);
+        }
+
+        let val = input.parse::<i32>().map_err(CharError::PercentParseError)?;
+
+        self.str_percentile = Some(val);
+
+        if (1..=100).contains(&val) {
+            Ok(Some(val))
+        } else {
+            Err(CharError::InvalidPercentile(Some(val)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::SeededRng;
+    use crate::level::LevelChange;
+
+    #[test]
+    fn ability_validation() {
+        let mut character = Character {
+            char_class: CharacterClass::Fighter,
+            ..Default::default()
+        };
+        match character.handle_ability_update(Ability::Dex, "abc") {
+            Err(CharError::AbilityParseError(Ability::Dex, _)) => (),
+            other => panic!("expected AbilityParseError, got {:?}", other),
+        }
+        match character.handle_ability_update(Ability::Con, "-3") {
+            Err(CharError::InvalidAbility(Ability::Con, -3)) => (),
+            other => panic!("expected InvalidAbility, got {:?}", other),
+        }
+        assert_eq!(character.con, 0);
+        match character.handle_ability_update(Ability::Str, "8") {
+            Err(CharError::ClassMinimum {
+                ability: Ability::Str,
+                min: 9,
+                ..
+            }) => (),
+            other => panic!("expected ClassMinimum, got {:?}", other),
+        }
+        // the score is kept so the derived values can still be shown
+        assert_eq!(character.str, 8);
+        assert_eq!(character.handle_ability_update(Ability::Str, "9").unwrap(), 9);
+    }
+
+    #[test]
+    fn racial_adjustments_and_limits() {
+        let mut character = Character {
+            char_class: CharacterClass::Fighter,
+            race: Race::Dwarf,
+            ..Default::default()
+        };
+        assert_eq!(character.handle_ability_update(Ability::Con, "17").unwrap(), 18);
+        assert_eq!(character.effective_ability(Ability::Con), 18);
+        match character.handle_ability_update(Ability::Con, "10") {
+            Err(CharError::RacialMinimum {
+                ability: Ability::Con,
+                min: 11,
+                race: Race::Dwarf,
+            }) => (),
+            other => panic!("expected RacialMinimum, got {:?}", other),
+        }
+        match character.handle_ability_update(Ability::Cha, "18") {
+            Err(CharError::RacialMaximum {
+                ability: Ability::Cha,
+                max: 17,
+                race: Race::Dwarf,
+            }) => (),
+            other => panic!("expected RacialMaximum, got {:?}", other),
+        }
+        character.char_class = CharacterClass::Paladin;
+        assert!(character.validate_class().is_err());
+        character.race = Race::Human;
+        assert!(character.validate_class().is_ok());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let character = Character {
+            char_name: "Tordek".to_string(),
+            char_class: CharacterClass::Fighter,
+            race: Race::Dwarf,
+            str: 18,
+            str_percentile: Some(42),
+            dex: 12,
+            con: 16,
+            int: 9,
+            wis: 10,
+            cha: 7,
+            ..Default::default()
+        };
+        let json = save_format::to_json(&character).unwrap();
+        let (loaded, errors) = Character::from_json_str(&json).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(save_format::to_json(&loaded).unwrap(), json);
+    }
+
+    #[test]
+    fn json_import_errors() {
+        let path = |json: &str| match Character::from_json_str(json) {
+            Err(errors) => match &errors[0] {
+                CharError::ImportError { path, .. } => path.clone(),
+                other => panic!("expected ImportError, got {:?}", other),
+            },
+            Ok(_) => panic!("expected {} to fail", json),
+        };
+        assert_eq!(path(r#"{"dex": "high"}"#), "dex");
+        assert_eq!(path(r#"{"char_class": "Samurai"}"#), "char_class");
+        assert_eq!(path(r#"{"strength": 12}"#), "strength");
+        assert_eq!(path(r#"{"dex": 12} trailing"#), ".");
+
+        let (character, errors) =
+            Character::from_json_str(r#"{"race": "Dwarf", "char_class": "Paladin", "cha": 30}"#)
+                .unwrap();
+        assert_eq!(character.cha, 30);
+        let paths = errors
+            .iter()
+            .map(|e| match e {
+                CharError::ImportError { path, .. } => path.as_str(),
+                _ => "",
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["cha", "char_class"]);
+    }
+
+    #[test]
+    fn exceptional_strength_for_warriors() {
+        let mut rng = SeededRng::new(5);
+        let mut character = Character {
+            char_class: CharacterClass::Ranger,
+            str: 18,
+            ..Default::default()
+        };
+        character.roll_exceptional_strength(&mut rng);
+        let per = character.str_percentile.unwrap();
+        assert!((1..=100).contains(&per));
+
+        character.char_class = CharacterClass::Thief;
+        character.roll_exceptional_strength(&mut rng);
+        assert_eq!(character.str_percentile, None);
+
+        // a halfling fighter's 18 is adjusted down to 17
+        character.char_class = CharacterClass::Fighter;
+        character.race = Race::Halfling;
+        character.roll_exceptional_strength(&mut rng);
+        assert_eq!(character.str_percentile, None);
+    }
+
+    #[test]
+    fn hit_points_follow_constitution() {
+        let mut character = Character {
+            char_class: CharacterClass::Fighter,
+            hit_points: HitPoints {
+                rolls: vec![10, 4],
+                damage: 3,
+                temporary: 0,
+            },
+            ..Default::default()
+        };
+        assert_eq!(character.max_hp(), 14);
+        character.con = 17;
+        assert_eq!(character.max_hp(), 20);
+        assert_eq!(character.current_hp(), 17);
+        // the warrior-only bonus goes away with the class
+        character.char_class = CharacterClass::Thief;
+        assert_eq!(character.max_hp(), 18);
+    }
+
+    #[test]
+    fn experience_and_levels() {
+        let mut character = Character {
+            char_class: CharacterClass::Paladin,
+            str: 16,
+            cha: 15,
+            hit_points: HitPoints {
+                rolls: vec![7],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(character.level(), 1);
+        assert_eq!(character.next_level_xp(), Some(2_250));
+        assert!(!character.xp_bonus());
+        assert_eq!(character.award_xp(2_000), None);
+        assert_eq!(character.xp, 2_000);
+
+        // both prime requisites have to be 16
+        character.cha = 17;
+        assert!(character.xp_bonus());
+        let up = character.award_xp(3_000).unwrap();
+        assert_eq!(character.xp, 5_300);
+        assert_eq!((up.from, up.to), (1, 3));
+        assert_eq!(
+            up.changes,
+            vec![
+                LevelChange::HitDice(2),
+                LevelChange::Thac0(18),
+                LevelChange::SavingThrows
+            ]
+        );
+        assert_eq!(character.hit_dice_owed(), 2);
+
+        // losing experience isn't a level up
+        assert_eq!(character.set_xp(0), None);
+        assert_eq!(character.level(), 1);
+    }
+
+    #[test]
+    fn attacks_combine_adjustments() {
+        let character = Character {
+            char_class: CharacterClass::Thief,
+            str: 17,
+            dex: 18,
+            xp: 2_500,
+            ..Default::default()
+        };
+        assert_eq!(character.thac0(), 19);
+        let melee = character.melee_attack(WeaponSkill::Proficient);
+        assert_eq!((melee.thac0(), melee.damage_adj()), (18, 1));
+        let missile = character.missile_attack(WeaponSkill::NonProficient);
+        // Dex +2, then the rogue non-proficiency penalty of -3
+        assert_eq!((missile.thac0(), missile.damage_adj()), (20, 0));
+    }
+
+    #[test]
+    fn saving_throws_with_racial_and_wisdom_bonuses() {
+        let character = Character {
+            char_class: CharacterClass::Fighter,
+            race: Race::Dwarf,
+            con: 16,
+            wis: 17,
+            ..Default::default()
+        };
+        let saves = character
+            .saving_throws()
+            .iter()
+            .map(|save| save.to_string())
+            .collect::<Vec<_>>();
+        // Con 17 after the dwarven adjustment is worth +4 against magic and poison
+        assert_eq!(
+            saves,
+            vec![
+                "14 (+4 vs poison)",
+                "12",
+                "15",
+                "17",
+                "13 (+3 vs mind-affecting spells)"
+            ]
+        );
+    }
+
+    #[test]
+    fn worn_armor_sets_protection() {
+        let mut character = Character::default();
+        let mail = character.add_item("Chain Mail", 1, Location::Pack).unwrap();
+        let shield = character.add_item("Small Shield", 1, Location::Worn).unwrap();
+        character.add_item("Torch", 2, Location::Carried).unwrap();
+        assert_eq!(character.protection.armor, ArmorType::None);
+        assert_eq!(character.protection.shield, ShieldType::Small);
+
+        character.move_item(mail, Location::Worn).unwrap();
+        assert_eq!(character.protection.armor, ArmorType::ChainMail);
+        assert_eq!(character.armor_class().normal(), 4);
+
+        character.remove_item(shield, None).unwrap();
+        assert_eq!(character.protection.shield, ShieldType::None);
+        assert_eq!(character.protection.armor, ArmorType::ChainMail);
+    }
+
+    #[test]
+    fn load_slows_and_hampers() {
+        let mut character = Character {
+            race: Race::Dwarf,
+            str: 12,
+            ..Default::default()
+        };
+        character.add_item("Chain Mail", 1, Location::Worn).unwrap();
+        assert_eq!(character.encumbrance(), Encumbrance::Unencumbered);
+        assert_eq!(character.movement(), 6);
+
+        character.add_item("Rope, Hemp (50')", 2, Location::Pack).unwrap();
+        assert_eq!(character.load(), 80.0);
+        assert_eq!(character.encumbrance(), Encumbrance::Moderate);
+        assert_eq!(character.movement(), 3);
+        let attack = character.melee_attack(WeaponSkill::Proficient);
+        assert_eq!(attack.thac0(), 21);
+        assert_eq!(
+            attack.to_string(),
+            "THAC0 21 (base 20, Encumbrance -1), damage +0"
+        );
+
+        character.add_item("Plate Mail", 1, Location::Carried).unwrap();
+        assert_eq!(character.encumbrance(), Encumbrance::Severe);
+        assert_eq!(character.movement(), 1);
+        assert_eq!(character.armor_class().normal(), 8);
+        // the mount carries it instead
+        character.move_item(2, Location::Mount).unwrap();
+        assert_eq!(character.encumbrance(), Encumbrance::Moderate);
+    }
+
+    #[test]
+    fn weapon_proficiencies() {
+        let mut character = Character {
+            char_class: CharacterClass::Fighter,
+            str: 12,
+            ..Default::default()
+        };
+        assert_eq!(character.weapon_slots(), 4);
+        character.learn_weapon("Long Sword").unwrap();
+        character.learn_weapon("Bows").unwrap();
+        character.specialize("Long Sword").unwrap();
+        assert_eq!(character.weapon_slots_left(), 0);
+        match character.specialize("Long Sword") {
+            Err(CharError::NoProficiencySlots { needed: 1, left: 0 }) => (),
+            other => panic!("expected NoProficiencySlots, got {:?}", other),
+        }
+
+        character.add_item("Dagger", 1, Location::Carried).unwrap();
+        let attacks = character
+            .weapon_attacks()
+            .iter()
+            .map(|attack| attack.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attacks,
+            vec![
+                "Dagger (Non-proficient): THAC0 22 (base 20, Non-proficient -2), damage +0, \
+                 1/1 attacks per round",
+                "Long Sword (Specialized): THAC0 19 (base 20, Specialized +1), damage +2 \
+                 (Specialized +2), 3/2 attacks per round",
+            ]
+        );
+
+        character.xp = 250_000;
+        character.specialize("Long Sword").unwrap();
+        let sword = character.weapon_attack("Long Sword").unwrap();
+        assert_eq!(sword.skill, WeaponSkill::DoubleSpecialized);
+        assert_eq!(sword.rate.to_string(), "2/1");
+        assert_eq!(
+            character.weapon_attack("Long Bow").unwrap().rate.to_string(),
+            "2/1"
+        );
+
+        character.char_class = CharacterClass::Ranger;
+        assert_eq!(character.weapon_skill("Long Sword"), WeaponSkill::Proficient);
+        assert!(character.specialize("Long Bow").is_err());
+        // a specialization set aside doesn't use slots until the character is a fighter again
+        assert_eq!(character.weapon_slots_used(), 3);
+        character.char_class = CharacterClass::Fighter;
+        assert_eq!(character.weapon_slots_used(), 5);
+    }
+
+    #[test]
+    fn buying_from_the_purse() {
+        let mut character = Character {
+            str: 12,
+            ..Default::default()
+        };
+        character.purse.gp = 2_300;
+        assert_eq!(character.load(), 46.0);
+        // the coins alone are enough to lightly encumber
+        assert_eq!(character.encumbrance(), Encumbrance::Light);
+
+        let index = character.buy_item("Long Sword", 1, Location::Carried).unwrap();
+        assert_eq!(character.inventory.items[index].name, "Long Sword");
+        assert_eq!(character.purse.value().unwrap(), 228_500);
+        assert_eq!(
+            character.purse.transactions[0].to_string(),
+            "Bought Long Sword for 15 gp, 2285 gp left"
+        );
+        character.purse.normalize().unwrap();
+        assert_eq!(character.purse.pp, 457);
+        assert_eq!(character.encumbrance(), Encumbrance::Unencumbered);
+
+        match character.buy_item("Full Plate", 1, Location::Worn) {
+            Err(CharError::NotEnoughMoney { .. }) => (),
+            other => panic!("expected NotEnoughMoney, got {:?}", other),
+        }
+        assert!(character.buy_item("Vorpal Sword", 1, Location::Worn).is_err());
+        assert!(character.buy_item("Dagger", 0, Location::Worn).is_err());
+        assert_eq!(character.inventory.items.len(), 1);
+        assert_eq!(character.purse.transactions.len(), 1);
+    }
+
+    #[test]
+    fn failed_purchase_keeps_the_money() {
+        let mut character = Character::default();
+        character.purse.gp = 10;
+        character.add_item("Torch", u32::MAX, Location::Pack).unwrap();
+        let purse = character.purse.clone();
+
+        match character.buy_item("Torch", 1, Location::Pack) {
+            Err(CharError::InvalidQuantity(1)) => (),
+            other => panic!("expected InvalidQuantity, got {:?}", other),
+        }
+        assert_eq!(character.purse, purse);
+        assert!(character.purse.transactions.is_empty());
+        assert_eq!(character.inventory.items[0].quantity, u32::MAX);
+    }
+}
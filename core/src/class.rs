@@ -0,0 +1,301 @@
+use crate::common::{Ability, CharError};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The groups classes are organized into. Most tables (hit dice, THAC0, saving throws, ...) go
+/// by class group rather than by individual class.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ClassGroup {
+    Warrior,
+    Wizard,
+    Priest,
+    Rogue,
+}
+
+impl Display for ClassGroup {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            ClassGroup::Warrior => write!(f, "Warrior"),
+            ClassGroup::Wizard => write!(f, "Wizard"),
+            ClassGroup::Priest => write!(f, "Priest"),
+            ClassGroup::Rogue => write!(f, "Rogue"),
+        }
+    }
+}
+
+impl ClassGroup {
+    /// Sides of the hit die rolled each level up to `hit_dice_levels`
+    pub fn hit_die(&self) -> u32 {
+        match self {
+            ClassGroup::Warrior => 10,
+            ClassGroup::Priest => 8,
+            ClassGroup::Rogue => 6,
+            ClassGroup::Wizard => 4,
+        }
+    }
+
+    /// Last level that rolls a hit die and gets the Constitution adjustment. Later levels add
+    /// `fixed_hp_gain` instead.
+    pub fn hit_dice_levels(&self) -> usize {
+        match self {
+            ClassGroup::Warrior | ClassGroup::Priest => 9,
+            ClassGroup::Rogue | ClassGroup::Wizard => 10,
+        }
+    }
+
+    pub fn fixed_hp_gain(&self) -> i32 {
+        match self {
+            ClassGroup::Warrior => 3,
+            ClassGroup::Priest | ClassGroup::Rogue => 2,
+            ClassGroup::Wizard => 1,
+        }
+    }
+
+    /// THAC0 at `level`. Warriors improve every level, the other groups in steps.
+    pub fn thac0(&self, level: u32) -> i32 {
+        let gained = level.saturating_sub(1) as i32;
+        let improvement = match self {
+            ClassGroup::Warrior => gained,
+            ClassGroup::Priest => gained / 3 * 2,
+            ClassGroup::Rogue => gained / 2,
+            ClassGroup::Wizard => gained / 3,
+        };
+        (20 - improvement).max(1)
+    }
+
+    /// Weapon proficiency slots at `level`: some to start with and one more every few levels
+    pub fn weapon_slots(&self, level: u32) -> u32 {
+        let (initial, every) = match self {
+            ClassGroup::Warrior => (4, 3),
+            ClassGroup::Wizard => (1, 6),
+            ClassGroup::Priest | ClassGroup::Rogue => (2, 4),
+        };
+        initial + level.saturating_sub(1) / every
+    }
+
+    /// Attack penalty for using a weapon the character isn't proficient with
+    pub fn non_proficiency_penalty(&self) -> i32 {
+        match self {
+            ClassGroup::Warrior => -2,
+            ClassGroup::Priest | ClassGroup::Rogue => -3,
+            ClassGroup::Wizard => -5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum CharacterClass {
+    #[default]
+    Fighter,
+    Paladin,
+    Ranger,
+    Mage,
+    Abjurer,
+    Conjurer,
+    Diviner,
+    Enchanter,
+    Illusionist,
+    Invoker,
+    Necromancer,
+    Transmuter,
+    Cleric,
+    Druid,
+    Thief,
+    Bard,
+}
+
+impl FromStr for CharacterClass {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Fighter" => Ok(CharacterClass::Fighter),
+            "Paladin" => Ok(CharacterClass::Paladin),
+            "Ranger" => Ok(CharacterClass::Ranger),
+            "Mage" => Ok(CharacterClass::Mage),
+            "Abjurer" => Ok(CharacterClass::Abjurer),
+            "Conjurer" => Ok(CharacterClass::Conjurer),
+            "Diviner" => Ok(CharacterClass::Diviner),
+            "Enchanter" => Ok(CharacterClass::Enchanter),
+            "Illusionist" => Ok(CharacterClass::Illusionist),
+            "Invoker" => Ok(CharacterClass::Invoker),
+            "Necromancer" => Ok(CharacterClass::Necromancer),
+            "Transmuter" => Ok(CharacterClass::Transmuter),
+            "Cleric" => Ok(CharacterClass::Cleric),
+            "Druid" => Ok(CharacterClass::Druid),
+            "Thief" => Ok(CharacterClass::Thief),
+            "Bard" => Ok(CharacterClass::Bard),
+            _ => Err(CharError::CharacterClassParseError(String::from(s))),
+        }
+    }
+}
+
+impl Display for CharacterClass {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            CharacterClass::Fighter => write!(f, "Fighter"),
+            CharacterClass::Paladin => write!(f, "Paladin"),
+            CharacterClass::Ranger => write!(f, "Ranger"),
+            CharacterClass::Mage => write!(f, "Mage"),
+            CharacterClass::Abjurer => write!(f, "Abjurer"),
+            CharacterClass::Conjurer => write!(f, "Conjurer"),
+            CharacterClass::Diviner => write!(f, "Diviner"),
+            CharacterClass::Enchanter => write!(f, "Enchanter"),
+            CharacterClass::Illusionist => write!(f, "Illusionist"),
+            CharacterClass::Invoker => write!(f, "Invoker"),
+            CharacterClass::Necromancer => write!(f, "Necromancer"),
+            CharacterClass::Transmuter => write!(f, "Transmuter"),
+            CharacterClass::Cleric => write!(f, "Cleric"),
+            CharacterClass::Druid => write!(f, "Druid"),
+            CharacterClass::Thief => write!(f, "Thief"),
+            CharacterClass::Bard => write!(f, "Bard"),
+        }
+    }
+}
+
+impl CharacterClass {
+    /// Every class, in the order they are listed in the class select
+    pub fn all() -> Vec<CharacterClass> {
+        vec![
+            CharacterClass::Fighter,
+            CharacterClass::Paladin,
+            CharacterClass::Ranger,
+            CharacterClass::Mage,
+            CharacterClass::Abjurer,
+            CharacterClass::Conjurer,
+            CharacterClass::Diviner,
+            CharacterClass::Enchanter,
+            CharacterClass::Illusionist,
+            CharacterClass::Invoker,
+            CharacterClass::Necromancer,
+            CharacterClass::Transmuter,
+            CharacterClass::Cleric,
+            CharacterClass::Druid,
+            CharacterClass::Thief,
+            CharacterClass::Bard,
+        ]
+    }
+
+    pub fn group(&self) -> ClassGroup {
+        match self {
+            CharacterClass::Fighter | CharacterClass::Paladin | CharacterClass::Ranger => {
+                ClassGroup::Warrior
+            }
+            CharacterClass::Mage
+            | CharacterClass::Abjurer
+            | CharacterClass::Conjurer
+            | CharacterClass::Diviner
+            | CharacterClass::Enchanter
+            | CharacterClass::Illusionist
+            | CharacterClass::Invoker
+            | CharacterClass::Necromancer
+            | CharacterClass::Transmuter => ClassGroup::Wizard,
+            CharacterClass::Cleric | CharacterClass::Druid => ClassGroup::Priest,
+            CharacterClass::Thief | CharacterClass::Bard => ClassGroup::Rogue,
+        }
+    }
+
+    pub fn is_warrior(&self) -> bool {
+        self.group() == ClassGroup::Warrior
+    }
+
+    /// Minimum score the class requires in `ability`, if any
+    pub fn ability_minimum(&self, ability: Ability) -> Option<i32> {
+        match (self, ability) {
+            (CharacterClass::Fighter, Ability::Str) => Some(9),
+            (CharacterClass::Paladin, Ability::Str) => Some(12),
+            (CharacterClass::Paladin, Ability::Con) => Some(9),
+            (CharacterClass::Paladin, Ability::Wis) => Some(13),
+            (CharacterClass::Paladin, Ability::Cha) => Some(17),
+            (CharacterClass::Ranger, Ability::Str) => Some(13),
+            (CharacterClass::Ranger, Ability::Dex) => Some(13),
+            (CharacterClass::Ranger, Ability::Con) => Some(14),
+            (CharacterClass::Ranger, Ability::Wis) => Some(14),
+            (CharacterClass::Abjurer, Ability::Wis) => Some(15),
+            (CharacterClass::Conjurer, Ability::Con) => Some(15),
+            (CharacterClass::Diviner, Ability::Wis) => Some(16),
+            (CharacterClass::Enchanter, Ability::Cha) => Some(16),
+            (CharacterClass::Illusionist, Ability::Dex) => Some(16),
+            (CharacterClass::Invoker, Ability::Con) => Some(16),
+            (CharacterClass::Necromancer, Ability::Wis) => Some(16),
+            (CharacterClass::Transmuter, Ability::Dex) => Some(15),
+            (_, Ability::Int) if self.group() == ClassGroup::Wizard => Some(9),
+            (CharacterClass::Cleric, Ability::Wis) => Some(9),
+            (CharacterClass::Druid, Ability::Wis) => Some(12),
+            (CharacterClass::Druid, Ability::Cha) => Some(15),
+            (CharacterClass::Thief, Ability::Dex) => Some(9),
+            (CharacterClass::Bard, Ability::Dex) => Some(12),
+            (CharacterClass::Bard, Ability::Int) => Some(13),
+            (CharacterClass::Bard, Ability::Cha) => Some(15),
+            _ => None,
+        }
+    }
+
+    /// Abilities that all have to be 16 or more for the 10% experience bonus
+    pub fn prime_requisites(&self) -> Vec<Ability> {
+        match self {
+            CharacterClass::Fighter => vec![Ability::Str],
+            CharacterClass::Paladin => vec![Ability::Str, Ability::Cha],
+            CharacterClass::Ranger => vec![Ability::Str, Ability::Dex, Ability::Wis],
+            CharacterClass::Cleric => vec![Ability::Wis],
+            CharacterClass::Druid => vec![Ability::Wis, Ability::Cha],
+            CharacterClass::Thief => vec![Ability::Dex],
+            CharacterClass::Bard => vec![Ability::Dex, Ability::Cha],
+            _ => vec![Ability::Int],
+        }
+    }
+
+    /// First level the class casts spells at, if it ever does
+    pub fn first_spell_level(&self) -> Option<u32> {
+        match self {
+            CharacterClass::Fighter | CharacterClass::Thief => None,
+            CharacterClass::Paladin => Some(9),
+            CharacterClass::Ranger => Some(8),
+            CharacterClass::Bard => Some(2),
+            _ => Some(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn character_class_round_trip() {
+        for cc in CharacterClass::all() {
+            assert_eq!(cc.to_string().parse::<CharacterClass>().unwrap(), cc);
+        }
+        assert!("Wizard".parse::<CharacterClass>().is_err());
+        assert_eq!(CharacterClass::Paladin.group(), ClassGroup::Warrior);
+        assert_eq!(CharacterClass::Illusionist.group(), ClassGroup::Wizard);
+        assert_eq!(CharacterClass::Druid.group(), ClassGroup::Priest);
+        assert_eq!(CharacterClass::Bard.group(), ClassGroup::Rogue);
+        assert_eq!(CharacterClass::Paladin.ability_minimum(Ability::Cha), Some(17));
+        assert_eq!(CharacterClass::Necromancer.ability_minimum(Ability::Int), Some(9));
+    }
+
+    #[test]
+    fn thac0_by_group() {
+        let at = |group: ClassGroup| (1..=20).map(|l| group.thac0(l)).collect::<Vec<_>>();
+        assert_eq!(at(ClassGroup::Warrior)[..3], [20, 19, 18]);
+        assert_eq!(ClassGroup::Warrior.thac0(20), 1);
+        assert_eq!(
+            at(ClassGroup::Priest)[..7],
+            [20, 20, 20, 18, 18, 18, 16]
+        );
+        assert_eq!(at(ClassGroup::Rogue)[..5], [20, 20, 19, 19, 18]);
+        assert_eq!(ClassGroup::Rogue.thac0(20), 11);
+        assert_eq!(ClassGroup::Wizard.thac0(20), 14);
+    }
+
+    #[test]
+    fn weapon_slots_by_group() {
+        let at = |group: ClassGroup| [1, 3, 4, 7, 13].map(|l| group.weapon_slots(l));
+        assert_eq!(at(ClassGroup::Warrior), [4, 4, 5, 6, 8]);
+        assert_eq!(at(ClassGroup::Priest), [2, 2, 2, 3, 5]);
+        assert_eq!(at(ClassGroup::Rogue), [2, 2, 2, 3, 5]);
+        assert_eq!(at(ClassGroup::Wizard), [1, 1, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::catalog::Missile;
+use crate::class::ClassGroup;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// How well the character knows the weapon being attacked with
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WeaponSkill {
+    NonProficient,
+    /// Not proficient, but proficient with a weapon in the same tight group
+    Familiar,
+    Proficient,
+    /// Fighters only, gives extra bonuses on top of being proficient
+    Specialized,
+    /// Fighters only, a further slot spent on a melee weapon already specialized in
+    DoubleSpecialized,
+}
+
+impl WeaponSkill {
+    pub fn hit_adj(&self, group: ClassGroup) -> i32 {
+        match self {
+            WeaponSkill::NonProficient => group.non_proficiency_penalty(),
+            // half the penalty, rounded up
+            WeaponSkill::Familiar => (group.non_proficiency_penalty() - 1) / 2,
+            WeaponSkill::Proficient => 0,
+            WeaponSkill::Specialized => 1,
+            WeaponSkill::DoubleSpecialized => 3,
+        }
+    }
+
+    pub fn damage_adj(&self) -> i32 {
+        match self {
+            WeaponSkill::Specialized => 2,
+            WeaponSkill::DoubleSpecialized => 3,
+            _ => 0,
+        }
+    }
+
+    pub fn is_specialized(&self) -> bool {
+        matches!(
+            self,
+            WeaponSkill::Specialized | WeaponSkill::DoubleSpecialized
+        )
+    }
+}
+
+impl Display for WeaponSkill {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            WeaponSkill::NonProficient => write!(f, "Non-proficient"),
+            WeaponSkill::Familiar => write!(f, "Familiar"),
+            WeaponSkill::Proficient => write!(f, "Proficient"),
+            WeaponSkill::Specialized => write!(f, "Specialized"),
+            WeaponSkill::DoubleSpecialized => write!(f, "Double Specialized"),
+        }
+    }
+}
+
+/// The numbers for one kind of attack, keeping each adjustment so the sheet can show where the
+/// totals come from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attack {
+    /// THAC0 for the class group and level
+    pub base_thac0: i32,
+    /// Adjustments to the attack roll. A bonus lowers the THAC0 needed.
+    pub hit_adjs: Vec<(String, i32)>,
+    pub damage_adjs: Vec<(String, i32)>,
+}
+
+impl Attack {
+    pub fn new(base_thac0: i32) -> Attack {
+        Attack {
+            base_thac0,
+            hit_adjs: vec![],
+            damage_adjs: vec![],
+        }
+    }
+
+    /// Adds an adjustment to the attack roll and one to damage, both labelled `source`
+    pub fn with(mut self, source: &str, hit_adj: i32, damage_adj: i32) -> Attack {
+        if hit_adj != 0 {
+            self.hit_adjs.push((source.to_string(), hit_adj));
+        }
+        if damage_adj != 0 {
+            self.damage_adjs.push((source.to_string(), damage_adj));
+        }
+        self
+    }
+
+    /// THAC0 with every adjustment to the attack roll applied
+    pub fn thac0(&self) -> i32 {
+        self.base_thac0 - self.hit_adjs.iter().map(|(_, adj)| adj).sum::<i32>()
+    }
+
+    pub fn damage_adj(&self) -> i32 {
+        self.damage_adjs.iter().map(|(_, adj)| adj).sum()
+    }
+}
+
+fn breakdown(adjs: &[(String, i32)]) -> String {
+    adjs.iter()
+        .map(|(source, adj)| format!("{} {:+}", source, adj))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// e.g. `THAC0 16 (base 19, Str +2, Specialized +1), damage +5 (Str +3, Specialized +2)`
+impl Display for Attack {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "THAC0 {}", self.thac0())?;
+        if !self.hit_adjs.is_empty() {
+            write!(
+                f,
+                " (base {}, {})",
+                self.base_thac0,
+                breakdown(&self.hit_adjs)
+            )?;
+        }
+        write!(f, ", damage {:+}", self.damage_adj())?;
+        if !self.damage_adjs.is_empty() {
+            write!(f, " ({})", breakdown(&self.damage_adjs))?;
+        }
+        Ok(())
+    }
+}
+
+/// Attacks per round, kept in half attacks so that e.g. 3/2 (three attacks every two rounds)
+/// can be written down
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttackRate {
+    pub half_attacks: u32,
+}
+
+impl AttackRate {
+    /// How often a character of `group` and `level` attacks with a weapon used as `missile`, or
+    /// in melee for `None`. Warriors get extra melee attacks as they go up in level, and
+    /// specialists more again with anything but a bow.
+    pub fn new(
+        group: ClassGroup,
+        level: u32,
+        missile: Option<Missile>,
+        skill: WeaponSkill,
+    ) -> AttackRate {
+        let tier = match level {
+            0..=6 => 0,
+            7..=12 => 1,
+            _ => 2,
+        };
+        let specialized = skill.is_specialized();
+        let half_attacks = match missile {
+            None if specialized => 3 + tier,
+            None if group == ClassGroup::Warrior => 2 + tier,
+            None => 2,
+            Some(Missile::Bow) => 4,
+            Some(Missile::Crossbow) if specialized => 2 + tier,
+            Some(Missile::Crossbow) => 2,
+            Some(Missile::Dart) if specialized => 8 + 2 * tier,
+            Some(Missile::Dart) => 6,
+            Some(Missile::Other) if specialized => 3 + tier,
+            Some(Missile::Other) | Some(Missile::Ammunition) => 2,
+        };
+        AttackRate { half_attacks }
+    }
+}
+
+/// e.g. `3/2` or `2/1`
+impl Display for AttackRate {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self.half_attacks % 2 {
+            0 => write!(f, "{}/1", self.half_attacks / 2),
+            _ => write!(f, "{}/2", self.half_attacks),
+        }
+    }
+}
+
+/// Everything about attacking with one weapon
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeaponAttack {
+    pub weapon: String,
+    pub skill: WeaponSkill,
+    pub attack: Attack,
+    pub rate: AttackRate,
+}
+
+/// e.g. `Long Sword (Specialized): THAC0 18 (base 20, Specialized +1), damage +2 (Specialized
+/// +2), 3/2 attacks per round`
+impl Display for WeaponAttack {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{} ({}): {}, {} attacks per round",
+            self.weapon, self.skill, self.attack, self.rate
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attack_adjustments() {
+        let attack =
+            Attack::new(19)
+                .with("Str", 2, 3)
+                .with(&WeaponSkill::Specialized.to_string(), 1, 2);
+        assert_eq!(attack.thac0(), 16);
+        assert_eq!(attack.damage_adj(), 5);
+        assert_eq!(
+            attack.to_string(),
+            "THAC0 16 (base 19, Str +2, Specialized +1), damage +5 (Str +3, Specialized +2)"
+        );
+
+        let group = ClassGroup::Wizard;
+        let clumsy = Attack::new(20).with(
+            "Non-proficient",
+            WeaponSkill::NonProficient.hit_adj(group),
+            0,
+        );
+        assert_eq!(clumsy.thac0(), 25);
+        assert_eq!(WeaponSkill::Familiar.hit_adj(group), -3);
+        assert_eq!(WeaponSkill::Familiar.hit_adj(ClassGroup::Warrior), -1);
+        assert_eq!(WeaponSkill::DoubleSpecialized.damage_adj(), 3);
+        assert_eq!(
+            Attack::new(20).with("Dex", 0, 0).to_string(),
+            "THAC0 20, damage +0"
+        );
+    }
+
+    #[test]
+    fn attacks_per_round() {
+        let rate = |group, level, missile, skill| {
+            AttackRate::new(group, level, missile, skill).to_string()
+        };
+        let warrior = ClassGroup::Warrior;
+        assert_eq!(rate(warrior, 1, None, WeaponSkill::Proficient), "1/1");
+        assert_eq!(rate(warrior, 7, None, WeaponSkill::Proficient), "3/2");
+        assert_eq!(rate(warrior, 13, None, WeaponSkill::Proficient), "2/1");
+        assert_eq!(rate(warrior, 1, None, WeaponSkill::Specialized), "3/2");
+        assert_eq!(
+            rate(warrior, 13, None, WeaponSkill::DoubleSpecialized),
+            "5/2"
+        );
+        assert_eq!(
+            rate(ClassGroup::Rogue, 13, None, WeaponSkill::Proficient),
+            "1/1"
+        );
+        let bow = Some(Missile::Bow);
+        assert_eq!(rate(warrior, 13, bow, WeaponSkill::Specialized), "2/1");
+        let dart = Some(Missile::Dart);
+        assert_eq!(
+            rate(ClassGroup::Wizard, 1, dart, WeaponSkill::NonProficient),
+            "3/1"
+        );
+        assert_eq!(rate(warrior, 7, dart, WeaponSkill::Specialized), "5/1");
+        let crossbow = Some(Missile::Crossbow);
+        assert_eq!(rate(warrior, 7, crossbow, WeaponSkill::Specialized), "3/2");
+    }
+}
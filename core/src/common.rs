@@ -0,0 +1,225 @@
+use crate::catalog;
+use crate::class::CharacterClass;
+use crate::purse::Coin;
+use crate::race::Race;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+
+pub type Result<T> = std::result::Result<T, CharError>;
+
+/// The six ability scores
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ability {
+    Str,
+    Dex,
+    Con,
+    Int,
+    Wis,
+    Cha,
+}
+
+impl Ability {
+    /// Name of the `Character` field holding this score
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            Ability::Str => "str",
+            Ability::Dex => "dex",
+            Ability::Con => "con",
+            Ability::Int => "int",
+            Ability::Wis => "wis",
+            Ability::Cha => "cha",
+        }
+    }
+
+    pub fn all() -> Vec<Ability> {
+        vec![
+            Ability::Str,
+            Ability::Dex,
+            Ability::Con,
+            Ability::Int,
+            Ability::Wis,
+            Ability::Cha,
+        ]
+    }
+}
+
+impl Display for Ability {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Ability::Str => write!(f, "Strength"),
+            Ability::Dex => write!(f, "Dexterity"),
+            Ability::Con => write!(f, "Constitution"),
+            Ability::Int => write!(f, "Intelligence"),
+            Ability::Wis => write!(f, "Wisdom"),
+            Ability::Cha => write!(f, "Charisma"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CharError {
+    AbilityParseError(Ability, ParseIntError),
+    PercentParseError(ParseIntError),
+    InvalidAbility(Ability, i32),
+    InvalidPercentile(Option<i32>),
+    ClassMinimum {
+        ability: Ability,
+        min: i32,
+        class: CharacterClass,
+    },
+    RacialMinimum {
+        ability: Ability,
+        min: i32,
+        race: Race,
+    },
+    RacialMaximum {
+        ability: Ability,
+        max: i32,
+        race: Race,
+    },
+    ClassNotAllowed {
+        race: Race,
+        class: CharacterClass,
+    },
+    CharacterClassParseError(String),
+    RaceParseError(String),
+    ImportError {
+        path: String,
+        reason: String,
+    },
+    StorageError(String),
+    UnsupportedVersion(u32),
+    DiceParseError {
+        expr: String,
+        reason: String,
+    },
+    RngError(String),
+    GenerationMethodParseError(String),
+    InvalidArrangement(String),
+    HitPointParseError(ParseIntError),
+    InvalidHitPoints(i32),
+    ExperienceParseError(ParseIntError),
+    ArmorParseError(String),
+    BonusParseError(ParseIntError),
+    UnknownItem(String),
+    LocationParseError(String),
+    QuantityParseError(ParseIntError),
+    InvalidQuantity(u32),
+    NoSuchItem(usize),
+    CoinParseError(String),
+    CoinsParseError(ParseIntError),
+    NotEnoughCoins {
+        coin: Coin,
+        held: u32,
+    },
+    NotEnoughMoney {
+        cost: u32,
+        available: u32,
+    },
+    ValueOverflow,
+    ValuableParseError(String),
+    NoSuchValuable(usize),
+    NotAWeapon(String),
+    AlreadyProficient(String),
+    NoProficiencySlots {
+        needed: u32,
+        left: u32,
+    },
+    CantSpecialize(String),
+}
+
+impl Display for CharError {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            CharError::AbilityParseError(a, pe) => write!(f, "{} must be a number ({})", a, pe),
+            CharError::PercentParseError(pe) => {
+                write!(f, "Strength percentile must be a number ({})", pe)
+            }
+            CharError::InvalidAbility(a, i) => {
+                write!(f, "{} must be between 1 and 25, not {}", a, i)
+            }
+            CharError::InvalidPercentile(Some(i)) => {
+                write!(f, "Strength percentile must be between 1 and 100, not {}", i)
+            }
+            CharError::InvalidPercentile(None) => write!(f, "Strength percentile is missing"),
+            CharError::ClassMinimum {
+                ability,
+                min,
+                class,
+            } => write!(f, "{} requires {} of at least {}", class, ability, min),
+            CharError::RacialMinimum { ability, min, race } => {
+                write!(f, "{} {} can't be lower than {}", race, ability, min)
+            }
+            CharError::RacialMaximum { ability, max, race } => {
+                write!(f, "{} {} can't be higher than {}", race, ability, max)
+            }
+            CharError::ClassNotAllowed { race, class } => {
+                write!(f, "{} can't be a {}", race, class)
+            }
+            CharError::CharacterClassParseError(i) => write!(f, "invalid class:{}", i),
+            CharError::RaceParseError(i) => write!(f, "invalid race:{}", i),
+            CharError::ImportError { path, reason } => write!(f, "{}: {}", path, reason),
+            CharError::StorageError(e) => write!(f, "storage error:{}", e),
+            CharError::UnsupportedVersion(v) => write!(
+                f,
+                "saved with a newer version of the sheet (format version {})",
+                v
+            ),
+            CharError::DiceParseError { expr, reason } => {
+                write!(f, "invalid dice expression {}: {}", expr, reason)
+            }
+            CharError::RngError(e) => write!(f, "no random numbers available:{}", e),
+            CharError::GenerationMethodParseError(i) => write!(f, "invalid method:{}", i),
+            CharError::InvalidArrangement(e) => write!(f, "can't use these scores: {}", e),
+            CharError::HitPointParseError(pe) => {
+                write!(f, "Hit points must be a number ({})", pe)
+            }
+            CharError::InvalidHitPoints(i) => {
+                write!(f, "Hit points can't be negative, not {}", i)
+            }
+            CharError::ExperienceParseError(pe) => {
+                write!(f, "Experience must be a whole number of points ({})", pe)
+            }
+            CharError::ArmorParseError(i) => write!(f, "invalid armor:{}", i),
+            CharError::BonusParseError(pe) => {
+                write!(f, "Magical bonus must be a whole number ({})", pe)
+            }
+            CharError::UnknownItem(i) => write!(f, "no item called {} in the catalog", i),
+            CharError::LocationParseError(i) => write!(f, "invalid location:{}", i),
+            CharError::QuantityParseError(pe) => {
+                write!(f, "Quantity must be a whole number ({})", pe)
+            }
+            CharError::InvalidQuantity(q) => write!(f, "Can't take a quantity of {}", q),
+            CharError::NoSuchItem(i) => write!(f, "no item number {} in the inventory", i),
+            CharError::CoinParseError(i) => write!(f, "invalid coin:{}", i),
+            CharError::CoinsParseError(pe) => {
+                write!(f, "Coins must be a whole number ({})", pe)
+            }
+            CharError::NotEnoughCoins { coin, held } => {
+                write!(f, "Only {} {} in the purse", held, coin)
+            }
+            CharError::NotEnoughMoney { cost, available } => write!(
+                f,
+                "Can't pay {} with {} in the purse",
+                catalog::format_cost(*cost),
+                catalog::format_cost(*available)
+            ),
+            CharError::ValueOverflow => {
+                write!(f, "Worth more than can be counted in copper pieces")
+            }
+            CharError::ValuableParseError(i) => write!(f, "invalid valuable:{}", i),
+            CharError::NoSuchValuable(i) => write!(f, "no gem or jewelry number {} in the purse", i),
+            CharError::NotAWeapon(i) => write!(f, "no weapon or weapon group called {}", i),
+            CharError::AlreadyProficient(i) => write!(f, "already proficient with {}", i),
+            CharError::NoProficiencySlots { needed, left } => write!(
+                f,
+                "{} weapon proficiency slots needed, only {} left",
+                needed, left
+            ),
+            CharError::CantSpecialize(reason) => write!(f, "can't specialize: {}", reason),
+        }
+    }
+}
+
+impl Error for CharError {}
@@ -0,0 +1,542 @@
+use crate::common::{CharError, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Most dice a single term may roll, and most sides a die may have
+const MAX_DICE: u32 = 100;
+const MAX_SIDES: u32 = 1000;
+/// An exploding die stops after this many extra rolls
+const MAX_EXPLOSIONS: usize = 100;
+/// Largest constant term, and most terms an expression may have. Together with the limits
+/// above they keep any total well inside an `i32`.
+const MAX_CONSTANT: u32 = 10_000;
+const MAX_TERMS: usize = 20;
+
+/// Source of randomness for rolling dice. Seed a `SeededRng` for repeatable rolls, the browser
+/// client plugs in the platform's crypto RNG.
+pub trait Rng {
+    fn next_u32(&mut self) -> u32;
+
+    /// A fair roll of a die with `sides` sides
+    fn roll_die(&mut self, sides: u32) -> u32 {
+        // reject the top sliver of the range so every face is equally likely
+        let zone = u32::MAX - u32::MAX % sides;
+        loop {
+            let n = self.next_u32();
+            if n < zone {
+                return n % sides + 1;
+            }
+        }
+    }
+}
+
+/// Small deterministic generator (SplitMix64), the same seed always gives the same rolls
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+}
+
+impl Rng for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        ((z ^ (z >> 31)) >> 32) as u32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Keep {
+    Highest(u32),
+    Lowest(u32),
+}
+
+/// `count` dice with `sides` sides, e.g. `4d6kh3` or `2d4x`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dice {
+    pub count: u32,
+    pub sides: u32,
+    pub keep: Option<Keep>,
+    /// Dice that roll their highest face are rolled again and added
+    pub explode: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Term {
+    Dice(Dice),
+    Constant(i32),
+}
+
+/// A parsed dice expression: terms that are added or subtracted, e.g. `3d6+2` or `1d20-1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiceExpr {
+    /// Each term with whether it is subtracted
+    pub terms: Vec<(bool, Term)>,
+}
+
+/// One die as rolled. Exploding dice have more than one roll.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DieRoll {
+    pub sides: u32,
+    pub rolls: Vec<u32>,
+    /// False for dice dropped by a keep highest/lowest
+    pub kept: bool,
+}
+
+impl DieRoll {
+    pub fn value(&self) -> i32 {
+        self.rolls.iter().sum::<u32>() as i32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermRoll {
+    pub negative: bool,
+    pub term: Term,
+    /// Empty for constants
+    pub dice: Vec<DieRoll>,
+    /// Value of the term, already negated if it is subtracted
+    pub total: i32,
+}
+
+/// The result of rolling a `DiceExpr`, with every die so the rolls can be shown
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roll {
+    pub expr: DiceExpr,
+    pub terms: Vec<TermRoll>,
+    pub total: i32,
+}
+
+impl Display for Keep {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Keep::Highest(n) => write!(f, "kh{}", n),
+            Keep::Lowest(n) => write!(f, "kl{}", n),
+        }
+    }
+}
+
+impl Display for Dice {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        if self.sides == 100 {
+            write!(f, "{}d%", self.count)?;
+        } else {
+            write!(f, "{}d{}", self.count, self.sides)?;
+        }
+        if let Some(keep) = self.keep {
+            write!(f, "{}", keep)?;
+        }
+        if self.explode {
+            write!(f, "x")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Term::Dice(dice) => write!(f, "{}", dice),
+            Term::Constant(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl Display for DiceExpr {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        for (i, (negative, term)) in self.terms.iter().enumerate() {
+            match (i, negative) {
+                (0, false) => (),
+                (_, true) => write!(f, "-")?,
+                (_, false) => write!(f, "+")?,
+            }
+            write!(f, "{}", term)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shows each die, e.g. `4d6kh3+1: [5, 3, (1), 6] + 1 = 15`. Dropped dice are in parentheses,
+/// exploded dice show each roll.
+impl Display for Roll {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{}:", self.expr)?;
+        for (i, term) in self.terms.iter().enumerate() {
+            match (i, term.negative) {
+                (0, false) => write!(f, " ")?,
+                (0, true) => write!(f, " -")?,
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            match term.term {
+                Term::Constant(n) => write!(f, "{}", n)?,
+                Term::Dice(_) => {
+                    let dice = term
+                        .dice
+                        .iter()
+                        .map(|die| {
+                            let rolls = die
+                                .rolls
+                                .iter()
+                                .map(|r| r.to_string())
+                                .collect::<Vec<_>>()
+                                .join("+");
+                            if die.kept {
+                                rolls
+                            } else {
+                                format!("({})", rolls)
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    write!(f, "[{}]", dice.join(", "))?
+                }
+            }
+        }
+        write!(f, " = {}", self.total)
+    }
+}
+
+/// Reads the digits at the start of `s`, returning the number and the rest
+fn take_number<'a>(s: &'a str, expr: &str) -> Result<(Option<u32>, &'a str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        return Ok((None, s));
+    }
+    let n = s[..end]
+        .parse::<u32>()
+        .map_err(|e| dice_error(expr, &e.to_string()))?;
+    Ok((Some(n), &s[end..]))
+}
+
+fn dice_error(expr: &str, reason: &str) -> CharError {
+    CharError::DiceParseError {
+        expr: expr.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn parse_term(term: &str, expr: &str) -> Result<Term> {
+    let (count, rest) = take_number(term, expr)?;
+    if rest.is_empty() {
+        let n = count.ok_or_else(|| dice_error(expr, "missing a term"))?;
+        if n > MAX_CONSTANT {
+            return Err(dice_error(
+                expr,
+                &format!("constants can be at most {}", MAX_CONSTANT),
+            ));
+        }
+        return Ok(Term::Constant(n as i32));
+    }
+    let rest = rest
+        .strip_prefix('d')
+        .ok_or_else(|| dice_error(expr, &format!("unexpected {}", rest)))?;
+    let count = count.unwrap_or(1);
+    let (sides, mut rest) = if let Some(rest) = rest.strip_prefix('%') {
+        (100, rest)
+    } else {
+        match take_number(rest, expr)? {
+            (Some(sides), rest) => (sides, rest),
+            (None, _) => return Err(dice_error(expr, "missing the number of sides")),
+        }
+    };
+
+    let mut dice = Dice {
+        count,
+        sides,
+        keep: None,
+        explode: false,
+    };
+    while !rest.is_empty() {
+        if rest.starts_with('x') && !dice.explode {
+            dice.explode = true;
+            rest = &rest[1..];
+        } else if rest.starts_with('k') && dice.keep.is_none() {
+            let (lowest, after) = if let Some(after) = rest.strip_prefix("kl") {
+                (true, after)
+            } else if let Some(after) = rest.strip_prefix("kh") {
+                (false, after)
+            } else {
+                (false, &rest[1..])
+            };
+            let (n, after) = take_number(after, expr)?;
+            let n = n.ok_or_else(|| dice_error(expr, "missing how many dice to keep"))?;
+            dice.keep = Some(if lowest {
+                Keep::Lowest(n)
+            } else {
+                Keep::Highest(n)
+            });
+            rest = after;
+        } else {
+            return Err(dice_error(expr, &format!("unexpected {}", rest)));
+        }
+    }
+
+    if dice.count == 0 || dice.count > MAX_DICE {
+        return Err(dice_error(
+            expr,
+            &format!("can roll 1 to {} dice at once", MAX_DICE),
+        ));
+    }
+    if dice.sides == 0 || dice.sides > MAX_SIDES {
+        return Err(dice_error(
+            expr,
+            &format!("dice must have 1 to {} sides", MAX_SIDES),
+        ));
+    }
+    if dice.explode && dice.sides == 1 {
+        return Err(dice_error(expr, "a d1 can't explode"));
+    }
+    match dice.keep {
+        Some(Keep::Highest(n)) | Some(Keep::Lowest(n)) if n == 0 || n > dice.count => {
+            return Err(dice_error(
+                expr,
+                &format!("can only keep 1 to {} dice", dice.count),
+            ))
+        }
+        _ => (),
+    }
+    Ok(Term::Dice(dice))
+}
+
+impl FromStr for DiceExpr {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let expr = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        if expr.is_empty() {
+            return Err(dice_error(s, "empty expression"));
+        }
+
+        let mut terms = vec![];
+        let mut rest = expr.as_str();
+        let mut negative = false;
+        if rest.starts_with('-') || rest.starts_with('+') {
+            negative = rest.starts_with('-');
+            rest = &rest[1..];
+        }
+        loop {
+            let end = rest.find(&['+', '-'][..]).unwrap_or(rest.len());
+            terms.push((negative, parse_term(&rest[..end], s)?));
+            if terms.len() > MAX_TERMS {
+                return Err(dice_error(
+                    s,
+                    &format!("can have at most {} terms", MAX_TERMS),
+                ));
+            }
+            if end == rest.len() {
+                break;
+            }
+            negative = rest[end..].starts_with('-');
+            rest = &rest[end + 1..];
+        }
+        Ok(DiceExpr { terms })
+    }
+}
+
+impl Dice {
+    /// Rolls every die, marking the ones a keep highest/lowest drops
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<DieRoll> {
+        let mut dice = (0..self.count)
+            .map(|_| {
+                let mut rolls = vec![rng.roll_die(self.sides)];
+                while self.explode
+                    && rolls.last() == Some(&self.sides)
+                    && rolls.len() <= MAX_EXPLOSIONS
+                {
+                    rolls.push(rng.roll_die(self.sides));
+                }
+                DieRoll {
+                    sides: self.sides,
+                    rolls,
+                    kept: true,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(keep) = self.keep {
+            // indexes from lowest to highest value, ties keep their order
+            let mut order = (0..dice.len()).collect::<Vec<_>>();
+            order.sort_by_key(|&i| dice[i].value());
+            let dropped = match keep {
+                Keep::Highest(n) => &order[..dice.len() - n as usize],
+                Keep::Lowest(n) => &order[n as usize..],
+            };
+            for &i in dropped {
+                dice[i].kept = false;
+            }
+        }
+        dice
+    }
+}
+
+impl DiceExpr {
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> Roll {
+        let terms = self
+            .terms
+            .iter()
+            .map(|&(negative, term)| {
+                let (dice, value) = match term {
+                    Term::Constant(n) => (vec![], n),
+                    Term::Dice(d) => {
+                        let dice = d.roll(rng);
+                        let value = dice.iter().filter(|die| die.kept).map(|die| die.value()).sum();
+                        (dice, value)
+                    }
+                };
+                TermRoll {
+                    negative,
+                    term,
+                    dice,
+                    total: if negative { -value } else { value },
+                }
+            })
+            .collect::<Vec<_>>();
+        Roll {
+            expr: self.clone(),
+            total: terms.iter().map(|t| t.total).sum(),
+            terms,
+        }
+    }
+}
+
+/// Parses and rolls `expr` in one go
+pub fn roll<R: Rng + ?Sized>(expr: &str, rng: &mut R) -> Result<Roll> {
+    Ok(expr.parse::<DiceExpr>()?.roll(rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out the given faces in order, so tests can pick every roll
+    struct Loaded(Vec<u32>);
+
+    impl Rng for Loaded {
+        fn next_u32(&mut self) -> u32 {
+            unreachable!()
+        }
+
+        fn roll_die(&mut self, _sides: u32) -> u32 {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let cases = vec![
+            ("4d6kh3", "4d6kh3"),
+            ("3d6+2", "3d6+2"),
+            ("1d20-1", "1d20-1"),
+            ("d%", "1d%"),
+            ("2d4x", "2d4x"),
+            (" 2D8 + 1d6 - 3 ", "2d8+1d6-3"),
+            ("4d6k3", "4d6kh3"),
+            ("2d20kl1", "2d20kl1"),
+            ("-2+d4", "-2+1d4"),
+        ];
+        for (expr, shown) in cases {
+            assert_eq!(expr.parse::<DiceExpr>().unwrap().to_string(), shown);
+        }
+        assert_eq!(
+            "4d6kh3".parse::<DiceExpr>().unwrap().terms,
+            vec![(
+                false,
+                Term::Dice(Dice {
+                    count: 4,
+                    sides: 6,
+                    keep: Some(Keep::Highest(3)),
+                    explode: false,
+                })
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for expr in &[
+            "", "d", "3d", "0d6", "3d0", "1d1x", "4d6kh5", "4d6kh0", "4d6k", "3d6+", "3d6++2",
+            "2d6y", "101d6", "1d6xx", "1d6kh1kl1", "abc", "2147483647+1", "10001",
+        ] {
+            match expr.parse::<DiceExpr>() {
+                Err(CharError::DiceParseError { .. }) => (),
+                other => panic!("expected {:?} to fail, got {:?}", expr, other),
+            }
+        }
+        let most = vec!["100d1000x"; MAX_TERMS].join("+");
+        assert!(format!("{}+1", most).parse::<DiceExpr>().is_err());
+    }
+
+    /// Rolls the highest face every time, so exploding dice explode as often as they can
+    struct Highest;
+
+    impl Rng for Highest {
+        fn next_u32(&mut self) -> u32 {
+            unreachable!()
+        }
+
+        fn roll_die(&mut self, sides: u32) -> u32 {
+            sides
+        }
+    }
+
+    #[test]
+    fn largest_roll_fits() {
+        let most = vec!["100d1000x"; MAX_TERMS].join("+");
+        let rolled = roll(&most, &mut Highest).unwrap();
+        assert_eq!(
+            rolled.total,
+            (MAX_TERMS * 100 * (MAX_EXPLOSIONS + 1) * 1000) as i32
+        );
+    }
+
+    #[test]
+    fn keep_highest_and_lowest() {
+        let rolled = roll("4d6kh3", &mut Loaded(vec![5, 3, 1, 6])).unwrap();
+        assert_eq!(rolled.total, 14);
+        let kept = rolled.terms[0].dice.iter().map(|d| d.kept).collect::<Vec<_>>();
+        assert_eq!(kept, vec![true, true, false, true]);
+        assert_eq!(rolled.to_string(), "4d6kh3: [5, 3, (1), 6] = 14");
+
+        let rolled = roll("2d20kl1+1", &mut Loaded(vec![17, 4])).unwrap();
+        assert_eq!(rolled.total, 5);
+    }
+
+    #[test]
+    fn exploding_and_modifiers() {
+        let rolled = roll("2d4x-1", &mut Loaded(vec![4, 4, 2, 3])).unwrap();
+        assert_eq!(rolled.terms[0].dice[0].rolls, vec![4, 4, 2]);
+        assert_eq!(rolled.terms[0].dice[1].rolls, vec![3]);
+        assert_eq!(rolled.total, 12);
+        assert_eq!(rolled.to_string(), "2d4x-1: [4+4+2, 3] - 1 = 12");
+
+        let rolled = roll("d%", &mut Loaded(vec![100])).unwrap();
+        assert_eq!(rolled.total, 100);
+    }
+
+    #[test]
+    fn seeded_rolls_repeat_and_stay_in_range() {
+        let expr = "10d6+1d20".parse::<DiceExpr>().unwrap();
+        let first = expr.roll(&mut SeededRng::new(7));
+        assert_eq!(first, expr.roll(&mut SeededRng::new(7)));
+
+        let mut rng = SeededRng::new(42);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let face = rng.roll_die(6);
+            assert!((1..=6).contains(&face));
+            seen[face as usize - 1] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::abilities::StrengthPercentile;
+use crate::common::Result;
+use std::fmt::{Display, Formatter};
+
+/// How much a load slows the character down, from the optional encumbrance rules
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Encumbrance {
+    Unencumbered,
+    Light,
+    Moderate,
+    Heavy,
+    Severe,
+    /// More than the character can lift, so they can't move at all
+    Overloaded,
+}
+
+impl Encumbrance {
+    /// The category for carrying `load` pounds with Strength `str`
+    pub fn for_load(str: &StrengthPercentile, load: f32) -> Result<Encumbrance> {
+        if load > str.max_press()? as f32 {
+            return Ok(Encumbrance::Overloaded);
+        }
+        let categories = [
+            Encumbrance::Unencumbered,
+            Encumbrance::Light,
+            Encumbrance::Moderate,
+            Encumbrance::Heavy,
+        ];
+        Ok(str
+            .encumbrance_limits()?
+            .iter()
+            .zip(categories.iter())
+            .find(|(limit, _)| load <= **limit as f32)
+            .map_or(Encumbrance::Severe, |(_, category)| *category))
+    }
+
+    /// Movement rate for a character whose unencumbered rate is `base`. Rates are rounded down,
+    /// but a character who can move at all manages at least 1.
+    pub fn movement(&self, base: i32) -> i32 {
+        match self {
+            Encumbrance::Unencumbered => base,
+            Encumbrance::Light => (base * 3 / 4).max(1),
+            Encumbrance::Moderate => (base / 2).max(1),
+            Encumbrance::Heavy => (base / 3).max(1),
+            Encumbrance::Severe => 1,
+            Encumbrance::Overloaded => 0,
+        }
+    }
+
+    /// Adjustment to attack rolls
+    pub fn hit_adj(&self) -> i32 {
+        match self {
+            Encumbrance::Unencumbered | Encumbrance::Light => 0,
+            Encumbrance::Moderate => -1,
+            Encumbrance::Heavy => -2,
+            Encumbrance::Severe | Encumbrance::Overloaded => -4,
+        }
+    }
+
+    /// Adjustment to Armor Class. Higher is worse.
+    pub fn ac_adj(&self) -> i32 {
+        match self {
+            Encumbrance::Unencumbered | Encumbrance::Light | Encumbrance::Moderate => 0,
+            Encumbrance::Heavy => 1,
+            Encumbrance::Severe | Encumbrance::Overloaded => 3,
+        }
+    }
+}
+
+impl Display for Encumbrance {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Encumbrance::Unencumbered => write!(f, "Unencumbered"),
+            Encumbrance::Light => write!(f, "Light"),
+            Encumbrance::Moderate => write!(f, "Moderate"),
+            Encumbrance::Heavy => write!(f, "Heavy"),
+            Encumbrance::Severe => write!(f, "Severe"),
+            Encumbrance::Overloaded => write!(f, "Overloaded"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strength(str: i32, per: Option<i32>) -> StrengthPercentile {
+        StrengthPercentile {
+            str,
+            per,
+            err: None,
+        }
+    }
+
+    #[test]
+    fn categories_by_strength() {
+        let average = strength(12, None);
+        let category = |load| Encumbrance::for_load(&average, load).unwrap();
+        assert_eq!(category(0.0), Encumbrance::Unencumbered);
+        assert_eq!(category(45.0), Encumbrance::Unencumbered);
+        assert_eq!(category(45.5), Encumbrance::Light);
+        assert_eq!(category(93.0), Encumbrance::Moderate);
+        assert_eq!(category(117.0), Encumbrance::Heavy);
+        assert_eq!(category(140.0), Encumbrance::Severe);
+        assert_eq!(category(141.0), Encumbrance::Overloaded);
+
+        let strong = strength(18, Some(100));
+        assert_eq!(
+            Encumbrance::for_load(&strong, 400.0).unwrap(),
+            Encumbrance::Moderate
+        );
+        assert!(Encumbrance::for_load(&strength(18, Some(101)), 0.0).is_err());
+    }
+
+    #[test]
+    fn movement_and_penalties() {
+        let rates = |base| {
+            [
+                Encumbrance::Unencumbered,
+                Encumbrance::Light,
+                Encumbrance::Moderate,
+                Encumbrance::Heavy,
+                Encumbrance::Severe,
+                Encumbrance::Overloaded,
+            ]
+            .iter()
+            .map(|category| category.movement(base))
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(rates(12), vec![12, 9, 6, 4, 1, 0]);
+        assert_eq!(rates(6), vec![6, 4, 3, 2, 1, 0]);
+        assert_eq!(Encumbrance::Heavy.hit_adj(), -2);
+        assert_eq!(Encumbrance::Severe.ac_adj(), 3);
+        assert_eq!(Encumbrance::Light.ac_adj(), 0);
+    }
+}
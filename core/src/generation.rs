@@ -0,0 +1,331 @@
+use crate::common::{Ability, CharError, Result};
+use crate::dice::{DiceExpr, Rng, Roll};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Highest score Method VI can build up to
+const METHOD_VI_MAX: i32 = 18;
+/// Every score starts at this before Method VI adds dice to it
+const METHOD_VI_BASE: i32 = 8;
+
+/// The ways of rolling ability scores from the Player's Handbook
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GenerationMethod {
+    /// 3d6 for each ability, in order
+    I,
+    /// 3d6 twice for each ability, keeping the better roll, in order
+    II,
+    /// 3d6 six times, arranged as the player likes
+    III,
+    /// 3d6 twelve times, the best six arranged as the player likes
+    IV,
+    /// 4d6 dropping the lowest die six times, arranged as the player likes
+    V,
+    /// Every ability starts at 8, then 7d6 are added one whole die at a time, up to 18
+    VI,
+}
+
+impl FromStr for GenerationMethod {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "I" => Ok(GenerationMethod::I),
+            "II" => Ok(GenerationMethod::II),
+            "III" => Ok(GenerationMethod::III),
+            "IV" => Ok(GenerationMethod::IV),
+            "V" => Ok(GenerationMethod::V),
+            "VI" => Ok(GenerationMethod::VI),
+            _ => Err(CharError::GenerationMethodParseError(String::from(s))),
+        }
+    }
+}
+
+impl Display for GenerationMethod {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            GenerationMethod::I => write!(f, "I"),
+            GenerationMethod::II => write!(f, "II"),
+            GenerationMethod::III => write!(f, "III"),
+            GenerationMethod::IV => write!(f, "IV"),
+            GenerationMethod::V => write!(f, "V"),
+            GenerationMethod::VI => write!(f, "VI"),
+        }
+    }
+}
+
+impl GenerationMethod {
+    pub fn all() -> Vec<GenerationMethod> {
+        vec![
+            GenerationMethod::I,
+            GenerationMethod::II,
+            GenerationMethod::III,
+            GenerationMethod::IV,
+            GenerationMethod::V,
+            GenerationMethod::VI,
+        ]
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            GenerationMethod::I => "3d6 in order",
+            GenerationMethod::II => "3d6 twice, keep the better, in order",
+            GenerationMethod::III => "3d6 six times, arrange",
+            GenerationMethod::IV => "3d6 twelve times, best six, arrange",
+            GenerationMethod::V => "4d6 drop lowest six times, arrange",
+            GenerationMethod::VI => "7d6 added to base 8s",
+        }
+    }
+
+    /// Whether the player picks which ability each value goes to
+    pub fn arranged(&self) -> bool {
+        !matches!(self, GenerationMethod::I | GenerationMethod::II)
+    }
+
+    fn roll_times<R: Rng + ?Sized>(expr: &str, times: usize, rng: &mut R) -> Vec<Roll> {
+        let expr = expr.parse::<DiceExpr>().expect("generation dice are valid");
+        (0..times).map(|_| expr.roll(rng)).collect()
+    }
+
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> Generated {
+        let (rolls, values) = match self {
+            GenerationMethod::I | GenerationMethod::III => {
+                let rolls = Self::roll_times("3d6", 6, rng);
+                let values = rolls.iter().map(|r| r.total).collect();
+                (rolls, values)
+            }
+            GenerationMethod::II => {
+                let rolls = Self::roll_times("3d6", 12, rng);
+                let values = rolls
+                    .chunks(2)
+                    .map(|pair| pair.iter().map(|r| r.total).max().unwrap_or(0))
+                    .collect();
+                (rolls, values)
+            }
+            GenerationMethod::IV => {
+                let rolls = Self::roll_times("3d6", 12, rng);
+                let mut values = rolls.iter().map(|r| r.total).collect::<Vec<_>>();
+                values.sort_by(|a, b| b.cmp(a));
+                values.truncate(6);
+                (rolls, values)
+            }
+            GenerationMethod::V => {
+                let rolls = Self::roll_times("4d6kh3", 6, rng);
+                let values = rolls.iter().map(|r| r.total).collect();
+                (rolls, values)
+            }
+            GenerationMethod::VI => {
+                let rolls = Self::roll_times("7d6", 1, rng);
+                let values = rolls[0].terms[0].dice.iter().map(|d| d.value()).collect();
+                (rolls, values)
+            }
+        };
+        Generated {
+            method: *self,
+            rolls,
+            values,
+        }
+    }
+}
+
+/// Freshly rolled values waiting to be assigned to abilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub method: GenerationMethod,
+    /// Every roll made, for showing the dice
+    pub rolls: Vec<Roll>,
+    /// The values to assign: six scores, or the seven dice for Method VI
+    pub values: Vec<i32>,
+}
+
+impl Generated {
+    /// The assignment to start from: each value to the abilities in order. Method VI has a die
+    /// more than there are abilities, so its two lowest dice share an ability, which keeps every
+    /// score at 18 or less whenever the dice can be arranged that way at all.
+    pub fn default_assignment(&self) -> Vec<Ability> {
+        let abilities = Ability::all();
+        if self.values.len() <= abilities.len() {
+            return abilities.into_iter().take(self.values.len()).collect();
+        }
+        // indexes of the values from lowest to highest, ties keep their order
+        let mut order = (0..self.values.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| self.values[i]);
+        let (first, second) = (order[0].min(order[1]), order[0].max(order[1]));
+
+        let mut in_order = abilities.iter().cycle();
+        let mut assignment: Vec<Ability> = Vec::with_capacity(self.values.len());
+        for i in 0..self.values.len() {
+            let ability = if i == second {
+                assignment[first]
+            } else {
+                *in_order.next().unwrap_or(&Ability::Str)
+            };
+            assignment.push(ability);
+        }
+        assignment
+    }
+
+    /// Scores in the order of `Ability::all()`, with `assignment[i]` saying which ability
+    /// `values[i]` goes to
+    pub fn scores(&self, assignment: &[Ability]) -> Result<Vec<i32>> {
+        if assignment.len() != self.values.len() {
+            return Err(CharError::InvalidArrangement(format!(
+                "expected {} assignments, got {}",
+                self.values.len(),
+                assignment.len()
+            )));
+        }
+        let abilities = Ability::all();
+        if !self.method.arranged() && assignment != abilities.as_slice() {
+            return Err(CharError::InvalidArrangement(format!(
+                "Method {} scores are used in order",
+                self.method
+            )));
+        }
+
+        if self.method == GenerationMethod::VI {
+            let mut scores = vec![METHOD_VI_BASE; abilities.len()];
+            for (value, ability) in self.values.iter().zip(assignment) {
+                let i = abilities.iter().position(|a| a == ability).unwrap_or(0);
+                scores[i] += value;
+                if scores[i] > METHOD_VI_MAX {
+                    return Err(CharError::InvalidArrangement(format!(
+                        "{} can't go above {}",
+                        ability, METHOD_VI_MAX
+                    )));
+                }
+            }
+            return Ok(scores);
+        }
+
+        abilities
+            .iter()
+            .map(|ability| {
+                let mut assigned = assignment.iter().zip(&self.values).filter(|(a, _)| *a == ability);
+                match (assigned.next(), assigned.next()) {
+                    (Some((_, value)), None) => Ok(*value),
+                    (None, _) => Err(CharError::InvalidArrangement(format!(
+                        "{} has no score",
+                        ability
+                    ))),
+                    (Some(_), Some(_)) => Err(CharError::InvalidArrangement(format!(
+                        "{} has more than one score",
+                        ability
+                    ))),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::SeededRng;
+
+    #[test]
+    fn methods_roll_in_range() {
+        let mut rng = SeededRng::new(3);
+        for method in GenerationMethod::all() {
+            assert_eq!(method.to_string().parse::<GenerationMethod>().unwrap(), method);
+            for _ in 0..50 {
+                let generated = method.roll(&mut rng);
+                let (count, range) = match method {
+                    GenerationMethod::VI => (7, 1..=6),
+                    _ => (6, 3..=18),
+                };
+                assert_eq!(generated.values.len(), count);
+                assert!(generated.values.iter().all(|v| range.contains(v)));
+            }
+        }
+    }
+
+    #[test]
+    fn method_ii_and_iv_keep_the_best() {
+        let mut rng = SeededRng::new(11);
+        let generated = GenerationMethod::II.roll(&mut rng);
+        for (pair, value) in generated.rolls.chunks(2).zip(&generated.values) {
+            assert_eq!(*value, pair[0].total.max(pair[1].total));
+        }
+
+        let generated = GenerationMethod::IV.roll(&mut rng);
+        let mut all = generated.rolls.iter().map(|r| r.total).collect::<Vec<_>>();
+        all.sort();
+        all.reverse();
+        assert_eq!(generated.values, all[..6].to_vec());
+    }
+
+    #[test]
+    fn arranging_scores() {
+        let generated = Generated {
+            method: GenerationMethod::III,
+            rolls: vec![],
+            values: vec![15, 12, 9, 17, 8, 11],
+        };
+        use Ability::*;
+        assert_eq!(
+            generated.scores(&[Wis, Dex, Cha, Str, Int, Con]).unwrap(),
+            vec![17, 12, 11, 8, 15, 9]
+        );
+        match generated.scores(&[Str, Str, Con, Int, Wis, Cha]) {
+            Err(CharError::InvalidArrangement(_)) => (),
+            other => panic!("expected InvalidArrangement, got {:?}", other),
+        }
+
+        let in_order = Generated {
+            method: GenerationMethod::I,
+            ..generated.clone()
+        };
+        assert!(in_order.scores(&in_order.default_assignment()).is_ok());
+        assert!(in_order.scores(&[Wis, Dex, Cha, Str, Int, Con]).is_err());
+    }
+
+    #[test]
+    fn method_vi_adds_dice_to_base_scores() {
+        let generated = Generated {
+            method: GenerationMethod::VI,
+            rolls: vec![],
+            values: vec![6, 4, 5, 1, 2, 3, 6],
+        };
+        use Ability::*;
+        assert_eq!(
+            generated.scores(&[Str, Str, Con, Int, Int, Int, Cha]).unwrap(),
+            vec![18, 8, 13, 14, 8, 14]
+        );
+        assert!(generated.scores(&[Str, Str, Str, Int, Int, Int, Cha]).is_err());
+    }
+
+    #[test]
+    fn default_assignment_is_valid() {
+        let mut rng = SeededRng::new(5);
+        for method in GenerationMethod::all() {
+            for _ in 0..200 {
+                let generated = method.roll(&mut rng);
+                let assignment = generated.default_assignment();
+                assert!(
+                    generated.scores(&assignment).is_ok(),
+                    "{:?} with {:?}",
+                    generated.values,
+                    assignment
+                );
+            }
+        }
+
+        // the two 5s share Dexterity rather than the 6s piling onto Strength
+        let generated = Generated {
+            method: GenerationMethod::VI,
+            rolls: vec![],
+            values: vec![6, 5, 5, 6, 6, 6, 6],
+        };
+        use Ability::*;
+        assert_eq!(
+            generated.default_assignment(),
+            vec![Str, Dex, Dex, Con, Int, Wis, Cha]
+        );
+        assert_eq!(
+            generated.scores(&generated.default_assignment()).unwrap(),
+            vec![14, 18, 14, 14, 14, 14]
+        );
+    }
+}
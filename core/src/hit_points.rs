@@ -0,0 +1,145 @@
+use crate::class::ClassGroup;
+use crate::common::{CharError, Result};
+use crate::dice::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Hit points as the rolls that make them up, so the maximum can be worked out again whenever
+/// Constitution or class changes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HitPoints {
+    /// What each level added before adjustments: the hit die rolled, or the fixed gain after
+    /// name level
+    pub rolls: Vec<i32>,
+    /// Damage taken and not healed yet
+    pub damage: i32,
+    /// Temporary hit points, lost before real ones
+    pub temporary: i32,
+}
+
+impl HitPoints {
+    /// Maximum hit points with `con_adj` applied to every level that rolled a hit die. A level
+    /// never adds less than 1.
+    pub fn max(&self, group: ClassGroup, con_adj: i32) -> i32 {
+        self.rolls
+            .iter()
+            .enumerate()
+            .map(|(level, roll)| {
+                if level < group.hit_dice_levels() {
+                    (roll + con_adj).max(1)
+                } else {
+                    *roll
+                }
+            })
+            .sum()
+    }
+
+    /// Hit points left, counting temporary ones. Negative once the character is down.
+    pub fn current(&self, group: ClassGroup, con_adj: i32) -> i32 {
+        self.max(group, con_adj) - self.damage + self.temporary
+    }
+
+    /// Adds the next level's hit die, or the fixed gain past name level, and returns what it
+    /// added before adjustments
+    pub fn roll_level<R: Rng + ?Sized>(&mut self, group: ClassGroup, rng: &mut R) -> i32 {
+        let roll = if self.rolls.len() < group.hit_dice_levels() {
+            rng.roll_die(group.hit_die()) as i32
+        } else {
+            group.fixed_hp_gain()
+        };
+        self.rolls.push(roll);
+        roll
+    }
+
+    /// Takes damage, from temporary hit points first
+    pub fn take_damage(&mut self, amount: i32) {
+        let absorbed = amount.min(self.temporary);
+        self.temporary -= absorbed;
+        self.damage += amount - absorbed;
+    }
+
+    /// Heals damage, up to the maximum
+    pub fn heal(&mut self, amount: i32) {
+        self.damage = (self.damage - amount).max(0);
+    }
+
+    /// Temporary hit points don't stack, the larger amount is kept
+    pub fn add_temporary(&mut self, amount: i32) {
+        self.temporary = self.temporary.max(amount);
+    }
+}
+
+/// Parses an amount of damage, healing or temporary hit points
+pub fn parse_amount(input: &str) -> Result<i32> {
+    let val = input.parse::<i32>().map_err(CharError::HitPointParseError)?;
+    if val < 0 {
+        return Err(CharError::InvalidHitPoints(val));
+    }
+    Ok(val)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::SeededRng;
+
+    #[test]
+    fn hit_dice_then_fixed_gains() {
+        let mut rng = SeededRng::new(9);
+        let mut hp = HitPoints::default();
+        for _ in 0..12 {
+            hp.roll_level(ClassGroup::Warrior, &mut rng);
+        }
+        assert!(hp.rolls[..9].iter().all(|r| (1..=10).contains(r)));
+        assert_eq!(&hp.rolls[9..], &[3, 3, 3]);
+
+        let hp = HitPoints {
+            rolls: vec![4, 1, 2],
+            ..Default::default()
+        };
+        assert_eq!(hp.max(ClassGroup::Wizard, 0), 7);
+        // Constitution applies to every level, but a level never adds less than 1
+        assert_eq!(hp.max(ClassGroup::Wizard, 2), 13);
+        assert_eq!(hp.max(ClassGroup::Wizard, -1), 5);
+
+        let hp = HitPoints {
+            rolls: vec![8, 8, 8, 8, 8, 8, 8, 8, 8, 2, 2],
+            ..Default::default()
+        };
+        // no Constitution bonus past name level
+        assert_eq!(hp.max(ClassGroup::Priest, 1), 9 * 9 + 4);
+    }
+
+    #[test]
+    fn damage_heal_and_temporary() {
+        let mut hp = HitPoints {
+            rolls: vec![10, 6],
+            ..Default::default()
+        };
+        hp.take_damage(5);
+        assert_eq!(hp.current(ClassGroup::Warrior, 0), 11);
+        hp.add_temporary(4);
+        hp.add_temporary(2);
+        assert_eq!(hp.current(ClassGroup::Warrior, 0), 15);
+        hp.take_damage(6);
+        assert_eq!(hp.temporary, 0);
+        assert_eq!(hp.damage, 7);
+        hp.heal(20);
+        assert_eq!(hp.current(ClassGroup::Warrior, 0), 16);
+        // a Constitution change is applied to the levels already rolled
+        assert_eq!(hp.current(ClassGroup::Warrior, 2), 20);
+    }
+
+    #[test]
+    fn amounts() {
+        assert_eq!(parse_amount("7").unwrap(), 7);
+        match parse_amount("lots") {
+            Err(CharError::HitPointParseError(_)) => (),
+            other => panic!("expected HitPointParseError, got {:?}", other),
+        }
+        match parse_amount("-2") {
+            Err(CharError::InvalidHitPoints(-2)) => (),
+            other => panic!("expected InvalidHitPoints, got {:?}", other),
+        }
+    }
+}
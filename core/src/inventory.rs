@@ -0,0 +1,316 @@
+use crate::catalog::{self, CatalogItem};
+use crate::common::{CharError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Where an item is kept, which decides whether it counts against what the character carries
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Location {
+    Worn,
+    Carried,
+    Pack,
+    /// Carried by the character's mount rather than the character
+    Mount,
+}
+
+impl FromStr for Location {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Location::all()
+            .into_iter()
+            .find(|location| location.to_string() == s)
+            .ok_or_else(|| CharError::LocationParseError(String::from(s)))
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Location::Worn => write!(f, "Worn"),
+            Location::Carried => write!(f, "Carried"),
+            Location::Pack => write!(f, "Pack"),
+            Location::Mount => write!(f, "Mount"),
+        }
+    }
+}
+
+impl Location {
+    pub fn all() -> Vec<Location> {
+        vec![
+            Location::Worn,
+            Location::Carried,
+            Location::Pack,
+            Location::Mount,
+        ]
+    }
+}
+
+/// A stack of identical items. Weight and cost are copied from the catalog when the item is
+/// added, so saved characters keep what they paid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Item {
+    pub name: String,
+    pub quantity: u32,
+    /// Weight of one, in pounds
+    pub weight: f32,
+    /// Cost of one, in copper pieces
+    pub cost: u32,
+    pub location: Location,
+}
+
+impl Item {
+    pub fn new(catalog_item: &CatalogItem, quantity: u32, location: Location) -> Item {
+        Item {
+            name: catalog_item.name.to_string(),
+            quantity,
+            weight: catalog_item.weight,
+            cost: catalog_item.cost,
+            location,
+        }
+    }
+
+    /// The catalog entry the item was bought from, if it is still there
+    pub fn catalog_item(&self) -> Option<&'static CatalogItem> {
+        catalog::find(&self.name)
+    }
+
+    fn stacks_with(&self, other: &Item) -> bool {
+        self.name == other.name
+            && self.location == other.location
+            && self.weight == other.weight
+            && self.cost == other.cost
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.weight * self.quantity as f32
+    }
+}
+
+/// e.g. `Flight Arrow x12 (Carried)`
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{}", self.name)?;
+        if self.quantity != 1 {
+            write!(f, " x{}", self.quantity)?;
+        }
+        write!(f, " ({})", self.location)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Inventory {
+    pub items: Vec<Item>,
+}
+
+impl Inventory {
+    /// Adds `quantity` of a catalog item, stacking with the same item already kept in the same
+    /// place. Returns the index of the stack.
+    pub fn add(&mut self, name: &str, quantity: u32, location: Location) -> Result<usize> {
+        let catalog_item =
+            catalog::find(name).ok_or_else(|| CharError::UnknownItem(name.to_string()))?;
+        if quantity == 0 {
+            return Err(CharError::InvalidQuantity(quantity));
+        }
+        self.stack(Item::new(catalog_item, quantity, location))
+    }
+
+    fn stack(&mut self, item: Item) -> Result<usize> {
+        match self.items.iter().position(|other| other.stacks_with(&item)) {
+            Some(i) => {
+                self.items[i].quantity = self.items[i]
+                    .quantity
+                    .checked_add(item.quantity)
+                    .ok_or(CharError::InvalidQuantity(item.quantity))?;
+                Ok(i)
+            }
+            None => {
+                self.items.push(item);
+                Ok(self.items.len() - 1)
+            }
+        }
+    }
+
+    fn get(&self, index: usize) -> Result<&Item> {
+        self.items.get(index).ok_or(CharError::NoSuchItem(index))
+    }
+
+    /// Removes `quantity` from the stack at `index`, or the whole stack for `None`, and returns
+    /// what was removed
+    pub fn remove(&mut self, index: usize, quantity: Option<u32>) -> Result<Item> {
+        let held = self.get(index)?.quantity;
+        let quantity = quantity.unwrap_or(held);
+        if quantity == 0 || quantity > held {
+            return Err(CharError::InvalidQuantity(quantity));
+        }
+        if quantity == held {
+            return Ok(self.items.remove(index));
+        }
+        self.items[index].quantity -= quantity;
+        Ok(Item {
+            quantity,
+            ..self.items[index].clone()
+        })
+    }
+
+    /// Moves the whole stack at `index` to `location`. It keeps its place in the list unless it
+    /// merges with a matching stack already there.
+    pub fn move_to(&mut self, index: usize, location: Location) -> Result<()> {
+        let moved = Item {
+            location,
+            ..self.get(index)?.clone()
+        };
+        let same = self
+            .items
+            .iter()
+            .enumerate()
+            .position(|(i, other)| i != index && other.stacks_with(&moved));
+        match same {
+            Some(same) => {
+                let quantity = self.items[same]
+                    .quantity
+                    .checked_add(moved.quantity)
+                    .ok_or(CharError::InvalidQuantity(moved.quantity))?;
+                self.items.remove(index);
+                let same = if same > index { same - 1 } else { same };
+                self.items[same].quantity = quantity;
+            }
+            None => self.items[index].location = location,
+        }
+        Ok(())
+    }
+
+    /// Pounds the character carries, leaving out whatever is on the mount
+    pub fn carried_weight(&self) -> f32 {
+        self.items
+            .iter()
+            .filter(|item| item.location != Location::Mount)
+            .fold(0.0, |total, item| total + item.total_weight())
+    }
+
+    pub fn mount_weight(&self) -> f32 {
+        self.items
+            .iter()
+            .filter(|item| item.location == Location::Mount)
+            .fold(0.0, |total, item| total + item.total_weight())
+    }
+
+    /// What everything cost, in copper pieces
+    pub fn value(&self) -> Result<u32> {
+        self.items.iter().try_fold(0u32, |total, item| {
+            item.cost
+                .checked_mul(item.quantity)
+                .and_then(|cost| total.checked_add(cost))
+                .ok_or(CharError::ValueOverflow)
+        })
+    }
+
+    /// Items kept in `location`
+    pub fn at(&self, location: Location) -> impl Iterator<Item = &Item> {
+        self.items
+            .iter()
+            .filter(move |item| item.location == location)
+    }
+}
+
+/// Parses a quantity typed into the sheet. An empty field means one for adding and everything
+/// for removing, so it is returned as `None`.
+pub fn parse_quantity(input: &str) -> Result<Option<u32>> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    input
+        .parse::<u32>()
+        .map(Some)
+        .map_err(CharError::QuantityParseError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_stacks_matching_items() {
+        let mut inventory = Inventory::default();
+        assert_eq!(inventory.add("Flight Arrow", 12, Location::Carried).unwrap(), 0);
+        assert_eq!(inventory.add("Long Sword", 1, Location::Carried).unwrap(), 1);
+        assert_eq!(inventory.add("Flight Arrow", 6, Location::Carried).unwrap(), 0);
+        assert_eq!(inventory.add("Flight Arrow", 6, Location::Pack).unwrap(), 2);
+        assert_eq!(inventory.items[0].quantity, 18);
+        assert_eq!(inventory.items[0].to_string(), "Flight Arrow x18 (Carried)");
+        match inventory.add("Vorpal Sword", 1, Location::Carried) {
+            Err(CharError::UnknownItem(_)) => (),
+            other => panic!("expected UnknownItem, got {:?}", other),
+        }
+        assert!(inventory.add("Dagger", 0, Location::Carried).is_err());
+    }
+
+    #[test]
+    fn remove_and_move() {
+        let mut inventory = Inventory::default();
+        inventory.add("Torch", 6, Location::Pack).unwrap();
+        inventory.add("Rope, Hemp (50')", 1, Location::Mount).unwrap();
+        assert_eq!(inventory.carried_weight(), 6.0);
+        assert_eq!(Inventory::default().carried_weight().to_string(), "0");
+        assert_eq!(inventory.mount_weight(), 20.0);
+
+        assert_eq!(inventory.remove(0, Some(2)).unwrap().quantity, 2);
+        assert_eq!(inventory.items[0].quantity, 4);
+        assert!(inventory.remove(0, Some(5)).is_err());
+        match inventory.remove(7, None) {
+            Err(CharError::NoSuchItem(7)) => (),
+            other => panic!("expected NoSuchItem, got {:?}", other),
+        }
+
+        inventory.move_to(1, Location::Pack).unwrap();
+        assert_eq!(inventory.items[1].location, Location::Pack);
+        assert_eq!(inventory.at(Location::Mount).count(), 0);
+        assert_eq!(inventory.carried_weight(), 24.0);
+        assert_eq!(inventory.value().unwrap(), 4 + 100);
+        inventory.remove(0, None).unwrap();
+        assert_eq!(inventory.items.len(), 1);
+
+        // moving onto a matching stack merges them
+        inventory.add("Torch", 1, Location::Carried).unwrap();
+        inventory.add("Torch", 2, Location::Pack).unwrap();
+        inventory.move_to(1, Location::Pack).unwrap();
+        assert_eq!(inventory.items.len(), 2);
+        assert_eq!(inventory.items[1].to_string(), "Torch x3 (Pack)");
+    }
+
+    #[test]
+    fn overflowing_stacks_are_rejected() {
+        let mut inventory = Inventory::default();
+        inventory.add("Torch", u32::MAX, Location::Pack).unwrap();
+        match inventory.add("Torch", 1, Location::Pack) {
+            Err(CharError::InvalidQuantity(1)) => (),
+            other => panic!("expected InvalidQuantity, got {:?}", other),
+        }
+        inventory.add("Torch", 1, Location::Carried).unwrap();
+        match inventory.move_to(1, Location::Pack) {
+            Err(CharError::InvalidQuantity(1)) => (),
+            other => panic!("expected InvalidQuantity, got {:?}", other),
+        }
+        // a failed move leaves the stack where it was
+        assert_eq!(inventory.items[0].quantity, u32::MAX);
+        assert_eq!(inventory.items[1].location, Location::Carried);
+
+        match inventory.value() {
+            Err(CharError::ValueOverflow) => (),
+            other => panic!("expected ValueOverflow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn quantities() {
+        assert_eq!(parse_quantity("").unwrap(), None);
+        assert_eq!(parse_quantity("3").unwrap(), Some(3));
+        match parse_quantity("-1") {
+            Err(CharError::QuantityParseError(_)) => (),
+            other => panic!("expected QuantityParseError, got {:?}", other),
+        }
+    }
+}
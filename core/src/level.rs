@@ -0,0 +1,197 @@
+use crate::class::CharacterClass;
+use crate::common::{CharError, Result};
+use crate::saves;
+use std::fmt::{Display, Formatter};
+
+/// Highest level the experience tables go to
+pub const MAX_LEVEL: u32 = 20;
+
+const WARRIOR_XP: [u32; MAX_LEVEL as usize] = [
+    0, 2_000, 4_000, 8_000, 16_000, 32_000, 64_000, 125_000, 250_000, 500_000, 750_000, 1_000_000,
+    1_250_000, 1_500_000, 1_750_000, 2_000_000, 2_250_000, 2_500_000, 2_750_000, 3_000_000,
+];
+/// Paladins and rangers
+const HOLY_WARRIOR_XP: [u32; MAX_LEVEL as usize] = [
+    0, 2_250, 4_500, 9_000, 18_000, 36_000, 75_000, 150_000, 300_000, 600_000, 900_000, 1_200_000,
+    1_500_000, 1_800_000, 2_100_000, 2_400_000, 2_700_000, 3_000_000, 3_300_000, 3_600_000,
+];
+/// Mages and every specialist
+const WIZARD_XP: [u32; MAX_LEVEL as usize] = [
+    0, 2_500, 5_000, 10_000, 20_000, 40_000, 60_000, 90_000, 135_000, 250_000, 375_000, 750_000,
+    1_125_000, 1_500_000, 1_875_000, 2_250_000, 2_625_000, 3_000_000, 3_375_000, 3_750_000,
+];
+const CLERIC_XP: [u32; MAX_LEVEL as usize] = [
+    0, 1_500, 3_000, 6_000, 13_000, 27_500, 55_000, 110_000, 225_000, 450_000, 675_000, 900_000,
+    1_125_000, 1_350_000, 1_575_000, 1_800_000, 2_025_000, 2_250_000, 2_475_000, 2_700_000,
+];
+const DRUID_XP: [u32; MAX_LEVEL as usize] = [
+    0, 2_000, 4_000, 7_500, 12_500, 20_000, 35_000, 60_000, 90_000, 125_000, 200_000, 300_000,
+    750_000, 1_500_000, 3_000_000, 3_500_000, 4_000_000, 4_500_000, 5_000_000, 5_500_000,
+];
+/// Thieves and bards
+const ROGUE_XP: [u32; MAX_LEVEL as usize] = [
+    0, 1_250, 2_500, 5_000, 10_000, 20_000, 40_000, 70_000, 110_000, 160_000, 220_000, 440_000,
+    660_000, 880_000, 1_100_000, 1_320_000, 1_540_000, 1_760_000, 1_980_000, 2_200_000,
+];
+
+/// Experience needed for each level, starting with level 1
+fn xp_table(class: CharacterClass) -> &'static [u32] {
+    match class {
+        CharacterClass::Fighter => &WARRIOR_XP,
+        CharacterClass::Paladin | CharacterClass::Ranger => &HOLY_WARRIOR_XP,
+        CharacterClass::Cleric => &CLERIC_XP,
+        CharacterClass::Druid => &DRUID_XP,
+        CharacterClass::Thief | CharacterClass::Bard => &ROGUE_XP,
+        _ => &WIZARD_XP,
+    }
+}
+
+/// The level `xp` experience points reach, up to `MAX_LEVEL`
+pub fn level_for_xp(class: CharacterClass, xp: u32) -> u32 {
+    xp_table(class)
+        .iter()
+        .filter(|needed| **needed <= xp)
+        .count() as u32
+}
+
+/// Experience points needed to reach `level`, if the tables go that far
+pub fn xp_for_level(class: CharacterClass, level: u32) -> Option<u32> {
+    let i = level.checked_sub(1)? as usize;
+    xp_table(class).get(i).copied()
+}
+
+/// Parses an amount of experience points
+pub fn parse_xp(input: &str) -> Result<u32> {
+    input
+        .parse::<u32>()
+        .map_err(CharError::ExperienceParseError)
+}
+
+/// Something on the sheet that needs attention after gaining levels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LevelChange {
+    /// Hit dice to roll before hit points catch up with the new level
+    HitDice(usize),
+    /// The new THAC0, when it improved
+    Thac0(i32),
+    /// At least one saving throw improved
+    SavingThrows,
+    /// The class gets new spells at one of the levels gained
+    Spells,
+}
+
+impl Display for LevelChange {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            LevelChange::HitDice(1) => write!(f, "roll 1 hit die"),
+            LevelChange::HitDice(n) => write!(f, "roll {} hit dice", n),
+            LevelChange::Thac0(thac0) => write!(f, "THAC0 {}", thac0),
+            LevelChange::SavingThrows => write!(f, "better saving throws"),
+            LevelChange::Spells => write!(f, "new spells"),
+        }
+    }
+}
+
+/// What changed when experience carried the character past one or more levels
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelUp {
+    pub from: u32,
+    pub to: u32,
+    pub changes: Vec<LevelChange>,
+}
+
+impl LevelUp {
+    /// Works out the changes for a `class` going from level `from` to `to` with `hit_dice`
+    /// rolled so far. Returns `None` unless a level was gained.
+    pub fn new(class: CharacterClass, from: u32, to: u32, hit_dice: usize) -> Option<LevelUp> {
+        if to <= from {
+            return None;
+        }
+        let mut changes = vec![];
+        let owed = (to as usize).saturating_sub(hit_dice);
+        if owed > 0 {
+            changes.push(LevelChange::HitDice(owed));
+        }
+        let thac0 = class.group().thac0(to);
+        if thac0 != class.group().thac0(from) {
+            changes.push(LevelChange::Thac0(thac0));
+        }
+        if saves::base_saves(class.group(), to) != saves::base_saves(class.group(), from) {
+            changes.push(LevelChange::SavingThrows);
+        }
+        if matches!(class.first_spell_level(), Some(first) if to >= first) {
+            changes.push(LevelChange::Spells);
+        }
+        Some(LevelUp { from, to, changes })
+    }
+}
+
+/// e.g. `Reached level 3: roll 2 hit dice, new spells`
+impl Display for LevelUp {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "Reached level {}", self.to)?;
+        for (i, change) in self.changes.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { ": " } else { ", " }, change)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_from_experience() {
+        for class in CharacterClass::all() {
+            let table = xp_table(class);
+            assert_eq!(table.len(), MAX_LEVEL as usize);
+            assert!(table.windows(2).all(|pair| pair[0] < pair[1]), "{}", class);
+            assert_eq!(level_for_xp(class, 0), 1);
+        }
+        assert_eq!(level_for_xp(CharacterClass::Fighter, 1_999), 1);
+        assert_eq!(level_for_xp(CharacterClass::Fighter, 2_000), 2);
+        assert_eq!(level_for_xp(CharacterClass::Thief, 70_000), 8);
+        assert_eq!(level_for_xp(CharacterClass::Illusionist, 135_000), 9);
+        assert_eq!(level_for_xp(CharacterClass::Druid, 9_999_999), MAX_LEVEL);
+        assert_eq!(xp_for_level(CharacterClass::Ranger, 3), Some(4_500));
+        assert_eq!(xp_for_level(CharacterClass::Cleric, 0), None);
+        assert_eq!(xp_for_level(CharacterClass::Cleric, 21), None);
+    }
+
+    #[test]
+    fn level_up_changes() {
+        assert_eq!(LevelUp::new(CharacterClass::Fighter, 3, 3, 3), None);
+        assert_eq!(
+            LevelUp::new(CharacterClass::Fighter, 1, 3, 1),
+            Some(LevelUp {
+                from: 1,
+                to: 3,
+                changes: vec![
+                    LevelChange::HitDice(2),
+                    LevelChange::Thac0(18),
+                    LevelChange::SavingThrows
+                ],
+            })
+        );
+        let up = LevelUp::new(CharacterClass::Paladin, 8, 9, 9).unwrap();
+        assert_eq!(
+            up.changes,
+            vec![
+                LevelChange::Thac0(12),
+                LevelChange::SavingThrows,
+                LevelChange::Spells
+            ]
+        );
+        assert_eq!(
+            up.to_string(),
+            "Reached level 9: THAC0 12, better saving throws, new spells"
+        );
+        let up = LevelUp::new(CharacterClass::Cleric, 1, 2, 1).unwrap();
+        assert_eq!(
+            up.to_string(),
+            "Reached level 2: roll 1 hit die, new spells"
+        );
+        assert!(parse_xp("-5").is_err());
+    }
+}
@@ -0,0 +1,23 @@
+//! The AD&D 2nd edition rules behind the character sheet: the character model, the ability, class
+//! and race tables, and saving characters. Nothing in here depends on the browser, so it can be
+//! used natively as well as from the wasm client.
+
+pub mod abilities;
+pub mod armor_class;
+pub mod catalog;
+pub mod character;
+pub mod class;
+pub mod combat;
+pub mod common;
+pub mod dice;
+pub mod encumbrance;
+pub mod generation;
+pub mod hit_points;
+pub mod inventory;
+pub mod level;
+pub mod proficiency;
+pub mod purse;
+pub mod race;
+pub mod save_format;
+pub mod saves;
+pub mod storage;
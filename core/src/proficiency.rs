@@ -0,0 +1,366 @@
+use crate::catalog::{self, Missile};
+use crate::combat::WeaponSkill;
+use crate::common::{CharError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupKind {
+    /// Weapons so alike that knowing one makes the others familiar
+    Tight,
+    /// Weapons used in the same general way
+    Broad,
+}
+
+/// Related weapons that can be learned together for fewer slots than one at a time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeaponGroup {
+    pub name: &'static str,
+    pub kind: GroupKind,
+    /// Catalog names of the weapons in the group
+    pub weapons: &'static [&'static str],
+}
+
+impl WeaponGroup {
+    /// Slots it takes to learn every weapon in the group
+    pub fn slots(&self) -> u32 {
+        match self.kind {
+            GroupKind::Tight => 2,
+            GroupKind::Broad => 3,
+        }
+    }
+
+    pub fn contains(&self, weapon: &str) -> bool {
+        self.weapons.contains(&weapon)
+    }
+}
+
+/// e.g. `Long Blades (tight group)`
+impl Display for WeaponGroup {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self.kind {
+            GroupKind::Tight => write!(f, "{} (tight group)", self.name),
+            GroupKind::Broad => write!(f, "{} (broad group)", self.name),
+        }
+    }
+}
+
+const fn group(
+    name: &'static str,
+    kind: GroupKind,
+    weapons: &'static [&'static str],
+) -> WeaponGroup {
+    WeaponGroup {
+        name,
+        kind,
+        weapons,
+    }
+}
+
+/// The weapon groups for the weapons in the catalog, tight groups first
+pub const WEAPON_GROUPS: &[WeaponGroup] = &[
+    group("Axes", GroupKind::Tight, &["Battle Axe", "Hand Axe"]),
+    group("Bows", GroupKind::Tight, &["Short Bow", "Long Bow"]),
+    group(
+        "Long Blades",
+        GroupKind::Tight,
+        &["Bastard Sword", "Broad Sword", "Long Sword", "Scimitar"],
+    ),
+    group(
+        "Maces",
+        GroupKind::Tight,
+        &["Footman's Mace", "Morning Star"],
+    ),
+    group("Short Blades", GroupKind::Tight, &["Dagger", "Short Sword"]),
+    group("Spears", GroupKind::Tight, &["Spear", "Javelin"]),
+    group(
+        "Blades",
+        GroupKind::Broad,
+        &[
+            "Dagger",
+            "Short Sword",
+            "Bastard Sword",
+            "Broad Sword",
+            "Long Sword",
+            "Scimitar",
+            "Two-Handed Sword",
+        ],
+    ),
+    group(
+        "Bludgeons",
+        GroupKind::Broad,
+        &[
+            "Club",
+            "Footman's Flail",
+            "Footman's Mace",
+            "Morning Star",
+            "Quarterstaff",
+            "Warhammer",
+        ],
+    ),
+    group(
+        "Polearms and Spears",
+        GroupKind::Broad,
+        &["Halberd", "Spear", "Javelin"],
+    ),
+];
+
+/// Looks up a weapon group by its name
+pub fn find_group(name: &str) -> Option<&'static WeaponGroup> {
+    WEAPON_GROUPS.iter().find(|group| group.name == name)
+}
+
+/// The weapon a fighter has specialized in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Specialization {
+    pub weapon: String,
+    pub double: bool,
+}
+
+impl Specialization {
+    /// Slots it takes on top of being proficient. Bows take two, double specialization one more.
+    pub fn slots(&self) -> u32 {
+        let bow = catalog::find(&self.weapon).and_then(|item| item.missile()) == Some(Missile::Bow);
+        let single = if bow { 2 } else { 1 };
+        if self.double {
+            single + 1
+        } else {
+            single
+        }
+    }
+}
+
+/// The weapons a character has spent proficiency slots on
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Proficiencies {
+    /// Catalog names of the weapons learned one at a time
+    pub weapons: Vec<String>,
+    /// Names of the weapon groups learned whole
+    pub groups: Vec<String>,
+    pub specialization: Option<Specialization>,
+}
+
+impl Proficiencies {
+    pub fn slots_used(&self) -> u32 {
+        let groups: u32 = self
+            .groups
+            .iter()
+            .filter_map(|name| find_group(name))
+            .map(|group| group.slots())
+            .sum();
+        let specialization = self.specialization.as_ref().map_or(0, |s| s.slots());
+        self.weapons.len() as u32 + groups + specialization
+    }
+
+    /// Proficient with `weapon`, on its own or through a group learned whole
+    pub fn is_proficient(&self, weapon: &str) -> bool {
+        self.weapons.iter().any(|learned| learned == weapon)
+            || self
+                .groups
+                .iter()
+                .filter_map(|name| find_group(name))
+                .any(|group| group.contains(weapon))
+    }
+
+    /// How well the character knows `weapon`. Weapons in a tight group with one the character
+    /// is proficient with are familiar.
+    pub fn skill(&self, weapon: &str) -> WeaponSkill {
+        match &self.specialization {
+            Some(s) if s.weapon == weapon && s.double => return WeaponSkill::DoubleSpecialized,
+            Some(s) if s.weapon == weapon => return WeaponSkill::Specialized,
+            _ => (),
+        }
+        if self.is_proficient(weapon) {
+            return WeaponSkill::Proficient;
+        }
+        let familiar = WEAPON_GROUPS
+            .iter()
+            .filter(|group| group.kind == GroupKind::Tight && group.contains(weapon))
+            .any(|group| group.weapons.iter().any(|other| self.is_proficient(other)));
+        if familiar {
+            WeaponSkill::Familiar
+        } else {
+            WeaponSkill::NonProficient
+        }
+    }
+
+    /// Slots it would take to learn `name`, a weapon or a weapon group
+    pub fn slots_for(&self, name: &str) -> Result<u32> {
+        if self
+            .weapons
+            .iter()
+            .chain(self.groups.iter())
+            .any(|learned| learned == name)
+        {
+            return Err(CharError::AlreadyProficient(name.to_string()));
+        }
+        if let Some(group) = find_group(name) {
+            return Ok(group.slots());
+        }
+        catalog::find_weapon(name).ok_or_else(|| CharError::NotAWeapon(name.to_string()))?;
+        Ok(1)
+    }
+
+    /// Learns `name`, a weapon or a weapon group. Checking there are slots for it is up to the
+    /// caller, see `slots_for`.
+    pub fn learn(&mut self, name: &str) -> Result<()> {
+        self.slots_for(name)?;
+        if find_group(name).is_some() {
+            self.groups.push(name.to_string());
+        } else {
+            self.weapons.push(name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Forgets a weapon or group, along with the specialization if the weapon isn't known any
+    /// more
+    pub fn forget(&mut self, name: &str) {
+        self.weapons.retain(|learned| learned != name);
+        self.groups.retain(|learned| learned != name);
+        let specialization = self.specialization.take();
+        self.specialization = specialization.filter(|s| self.is_proficient(&s.weapon));
+    }
+
+    /// The specialization that specializing in `weapon` would give: a first specialization, or
+    /// double specialization in the melee weapon already specialized in
+    pub fn next_specialization(&self, weapon: &str) -> Result<Specialization> {
+        let item = catalog::find_weapon(weapon)
+            .ok_or_else(|| CharError::NotAWeapon(weapon.to_string()))?;
+        if !self.is_proficient(weapon) {
+            return Err(CharError::CantSpecialize(format!(
+                "not proficient with {}",
+                weapon
+            )));
+        }
+        match &self.specialization {
+            None => Ok(Specialization {
+                weapon: weapon.to_string(),
+                double: false,
+            }),
+            Some(s) if s.weapon != weapon => Err(CharError::CantSpecialize(format!(
+                "already specialized in {}",
+                s.weapon
+            ))),
+            Some(s) if s.double => Err(CharError::CantSpecialize(format!(
+                "already double specialized in {}",
+                weapon
+            ))),
+            Some(_) if item.missile().is_some() => Err(CharError::CantSpecialize(
+                "only melee weapons can be double specialized in".to_string(),
+            )),
+            Some(_) => Ok(Specialization {
+                weapon: weapon.to_string(),
+                double: true,
+            }),
+        }
+    }
+}
+
+/// e.g. `Long Sword (Specialized), Bows (tight group)`
+impl Display for Proficiencies {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        let weapons = self
+            .weapons
+            .iter()
+            .map(|weapon| match self.skill(weapon) {
+                WeaponSkill::Proficient => weapon.to_string(),
+                skill => format!("{} ({})", weapon, skill),
+            })
+            .chain(
+                self.groups
+                    .iter()
+                    .filter_map(|name| find_group(name))
+                    .map(|group| group.to_string()),
+            )
+            .collect::<Vec<_>>();
+        if weapons.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", weapons.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_cover_catalog_weapons() {
+        for group in WEAPON_GROUPS {
+            for weapon in group.weapons {
+                assert!(
+                    catalog::find_weapon(weapon).is_some(),
+                    "{} in {} isn't a catalog weapon",
+                    weapon,
+                    group.name
+                );
+            }
+        }
+        assert_eq!(
+            find_group("Bows").unwrap().to_string(),
+            "Bows (tight group)"
+        );
+    }
+
+    #[test]
+    fn learning_and_skills() {
+        let mut proficiencies = Proficiencies::default();
+        assert_eq!(proficiencies.slots_for("Long Sword").unwrap(), 1);
+        assert_eq!(proficiencies.slots_for("Bludgeons").unwrap(), 3);
+        proficiencies.learn("Long Sword").unwrap();
+        proficiencies.learn("Bows").unwrap();
+        assert_eq!(proficiencies.slots_used(), 3);
+        match proficiencies.learn("Long Sword") {
+            Err(CharError::AlreadyProficient(_)) => (),
+            other => panic!("expected AlreadyProficient, got {:?}", other),
+        }
+        match proficiencies.learn("Torch") {
+            Err(CharError::NotAWeapon(_)) => (),
+            other => panic!("expected NotAWeapon, got {:?}", other),
+        }
+
+        assert_eq!(proficiencies.skill("Long Sword"), WeaponSkill::Proficient);
+        assert_eq!(proficiencies.skill("Long Bow"), WeaponSkill::Proficient);
+        assert_eq!(proficiencies.skill("Scimitar"), WeaponSkill::Familiar);
+        // a broad group doesn't make its weapons familiar
+        assert_eq!(proficiencies.skill("Dagger"), WeaponSkill::NonProficient);
+        assert_eq!(proficiencies.to_string(), "Long Sword, Bows (tight group)");
+    }
+
+    #[test]
+    fn specializing() {
+        let mut proficiencies = Proficiencies::default();
+        proficiencies.learn("Long Sword").unwrap();
+        proficiencies.learn("Short Bow").unwrap();
+        assert!(proficiencies.next_specialization("Dagger").is_err());
+
+        let bow = proficiencies.next_specialization("Short Bow").unwrap();
+        assert_eq!(bow.slots(), 2);
+        proficiencies.specialization = Some(bow);
+        assert!(proficiencies.next_specialization("Short Bow").is_err());
+        proficiencies.forget("Short Bow");
+        assert_eq!(proficiencies.specialization, None);
+
+        proficiencies.specialization = proficiencies.next_specialization("Long Sword").ok();
+        assert!(
+            proficiencies
+                .next_specialization("Long Sword")
+                .unwrap()
+                .double
+        );
+        proficiencies.specialization = proficiencies.next_specialization("Long Sword").ok();
+        assert_eq!(proficiencies.slots_used(), 3);
+        assert_eq!(
+            proficiencies.skill("Long Sword"),
+            WeaponSkill::DoubleSpecialized
+        );
+        match proficiencies.next_specialization("Long Sword") {
+            Err(CharError::CantSpecialize(_)) => (),
+            other => panic!("expected CantSpecialize, got {:?}", other),
+        }
+    }
+}
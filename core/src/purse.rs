@@ -0,0 +1,435 @@
+use crate::catalog::{self, CatalogItem};
+use crate::common::{CharError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Coins to the pound
+pub const COINS_PER_LB: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Coin {
+    Copper,
+    Silver,
+    Electrum,
+    Gold,
+    Platinum,
+}
+
+impl Coin {
+    /// From the least to the most valuable
+    pub fn all() -> Vec<Coin> {
+        vec![
+            Coin::Copper,
+            Coin::Silver,
+            Coin::Electrum,
+            Coin::Gold,
+            Coin::Platinum,
+        ]
+    }
+
+    /// Worth of one coin in copper pieces
+    pub fn value(&self) -> u32 {
+        match self {
+            Coin::Copper => 1,
+            Coin::Silver => 10,
+            Coin::Electrum => 50,
+            Coin::Gold => 100,
+            Coin::Platinum => 500,
+        }
+    }
+}
+
+impl FromStr for Coin {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Coin::all()
+            .into_iter()
+            .find(|coin| coin.to_string() == s)
+            .ok_or_else(|| CharError::CoinParseError(String::from(s)))
+    }
+}
+
+impl Display for Coin {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Coin::Copper => write!(f, "cp"),
+            Coin::Silver => write!(f, "sp"),
+            Coin::Electrum => write!(f, "ep"),
+            Coin::Gold => write!(f, "gp"),
+            Coin::Platinum => write!(f, "pp"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ValuableKind {
+    Gem,
+    Jewelry,
+}
+
+impl FromStr for ValuableKind {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ValuableKind::all()
+            .into_iter()
+            .find(|kind| kind.to_string() == s)
+            .ok_or_else(|| CharError::ValuableParseError(String::from(s)))
+    }
+}
+
+impl Display for ValuableKind {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            ValuableKind::Gem => write!(f, "Gem"),
+            ValuableKind::Jewelry => write!(f, "Jewelry"),
+        }
+    }
+}
+
+impl ValuableKind {
+    pub fn all() -> Vec<ValuableKind> {
+        vec![ValuableKind::Gem, ValuableKind::Jewelry]
+    }
+}
+
+/// A gem or piece of jewelry, light enough that its weight doesn't count
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Valuable {
+    pub kind: ValuableKind,
+    pub description: String,
+    /// Worth in copper pieces
+    pub value: u32,
+}
+
+/// e.g. `Gem: Pearl (100 gp)`
+impl Display for Valuable {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{}: {} ({})",
+            self.kind,
+            self.description,
+            catalog::format_cost(self.value)
+        )
+    }
+}
+
+/// A purchase from the catalog, kept so spending can be checked later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Transaction {
+    pub item: String,
+    pub quantity: u32,
+    /// What was paid in copper pieces
+    pub cost: u32,
+    /// Worth of the coins left in the purse afterwards, in copper pieces
+    pub balance: u32,
+}
+
+/// e.g. `Bought Torch x3 for 3 cp, 15 gp left`
+impl Display for Transaction {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "Bought {}", self.item)?;
+        if self.quantity != 1 {
+            write!(f, " x{}", self.quantity)?;
+        }
+        write!(
+            f,
+            " for {}, {} left",
+            catalog::format_cost(self.cost),
+            catalog::format_cost(self.balance)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Purse {
+    pub cp: u32,
+    pub sp: u32,
+    pub ep: u32,
+    pub gp: u32,
+    pub pp: u32,
+    pub valuables: Vec<Valuable>,
+    /// Every purchase, oldest first
+    pub transactions: Vec<Transaction>,
+}
+
+impl Purse {
+    pub fn coins(&self, coin: Coin) -> u32 {
+        match coin {
+            Coin::Copper => self.cp,
+            Coin::Silver => self.sp,
+            Coin::Electrum => self.ep,
+            Coin::Gold => self.gp,
+            Coin::Platinum => self.pp,
+        }
+    }
+
+    pub fn coins_mut(&mut self, coin: Coin) -> &mut u32 {
+        match coin {
+            Coin::Copper => &mut self.cp,
+            Coin::Silver => &mut self.sp,
+            Coin::Electrum => &mut self.ep,
+            Coin::Gold => &mut self.gp,
+            Coin::Platinum => &mut self.pp,
+        }
+    }
+
+    /// Worth of the coins in copper pieces
+    pub fn value(&self) -> Result<u32> {
+        Coin::all().into_iter().try_fold(0u32, |total, coin| {
+            self.coins(coin)
+                .checked_mul(coin.value())
+                .and_then(|worth| total.checked_add(worth))
+                .ok_or(CharError::ValueOverflow)
+        })
+    }
+
+    /// Worth of the gems and jewelry in copper pieces
+    pub fn valuables_value(&self) -> Result<u32> {
+        self.valuables
+            .iter()
+            .try_fold(0u32, |total, valuable| total.checked_add(valuable.value))
+            .ok_or(CharError::ValueOverflow)
+    }
+
+    /// Pounds the coins weigh
+    pub fn weight(&self) -> f32 {
+        let coins: f32 = Coin::all()
+            .into_iter()
+            .map(|coin| self.coins(coin) as f32)
+            .sum();
+        coins / COINS_PER_LB as f32
+    }
+
+    /// Puts `value` copper pieces' worth into the purse in as few coins as possible
+    fn add_value(&mut self, mut value: u32) {
+        for coin in Coin::all().into_iter().rev() {
+            *self.coins_mut(coin) += value / coin.value();
+            value %= coin.value();
+        }
+    }
+
+    /// Exchanges the coins for as few coins as possible, which also makes them lighter
+    pub fn normalize(&mut self) -> Result<()> {
+        let value = self.value()?;
+        for coin in Coin::all() {
+            *self.coins_mut(coin) = 0;
+        }
+        self.add_value(value);
+        Ok(())
+    }
+
+    /// Exchanges `amount` coins of one kind for another at the standard rates. Only as many coins
+    /// as buy whole coins of the other kind change hands. Returns how many coins were received.
+    pub fn exchange(&mut self, from: Coin, amount: u32, to: Coin) -> Result<u32> {
+        let held = self.coins(from);
+        if amount > held {
+            return Err(CharError::NotEnoughCoins { coin: from, held });
+        }
+        let worth = amount
+            .checked_mul(from.value())
+            .ok_or(CharError::ValueOverflow)?;
+        let received = worth / to.value();
+        let paid = (worth - worth % to.value()).div_ceil(from.value());
+        *self.coins_mut(to) = self
+            .coins(to)
+            .checked_add(received)
+            .ok_or(CharError::ValueOverflow)?;
+        *self.coins_mut(from) -= paid;
+        Ok(received)
+    }
+
+    /// Pays `cost` copper pieces, cheapest coins first so the purse gets lighter. When the coins
+    /// don't add up exactly, the next coin up is broken and the change comes back in as few coins
+    /// as possible.
+    pub fn spend(&mut self, cost: u32) -> Result<()> {
+        let available = self.value()?;
+        if cost > available {
+            return Err(CharError::NotEnoughMoney { cost, available });
+        }
+        let mut owed = cost;
+        for coin in Coin::all() {
+            let paid = self.coins(coin).min(owed / coin.value());
+            *self.coins_mut(coin) -= paid;
+            // never more than `owed`, as no more coins are paid than it takes
+            owed -= paid
+                .checked_mul(coin.value())
+                .ok_or(CharError::ValueOverflow)?;
+        }
+        if owed > 0 {
+            // every coin left is worth more than what is still owed
+            let coin = Coin::all()
+                .into_iter()
+                .find(|coin| self.coins(*coin) > 0)
+                .expect("the purse holds enough to pay");
+            *self.coins_mut(coin) -= 1;
+            self.add_value(coin.value() - owed);
+        }
+        Ok(())
+    }
+
+    /// Pays for `quantity` of a catalog item and writes the purchase in the log
+    pub fn buy(&mut self, item: &CatalogItem, quantity: u32) -> Result<&Transaction> {
+        let cost = item
+            .cost
+            .checked_mul(quantity)
+            .ok_or(CharError::InvalidQuantity(quantity))?;
+        self.spend(cost)?;
+        self.transactions.push(Transaction {
+            item: item.name.to_string(),
+            quantity,
+            cost,
+            balance: self.value()?,
+        });
+        Ok(&self.transactions[self.transactions.len() - 1])
+    }
+
+    pub fn remove_valuable(&mut self, index: usize) -> Result<Valuable> {
+        if index >= self.valuables.len() {
+            return Err(CharError::NoSuchValuable(index));
+        }
+        Ok(self.valuables.remove(index))
+    }
+}
+
+/// e.g. `2 pp 15 gp 3 cp`, leaving out the coins there are none of
+impl Display for Purse {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        let coins = Coin::all()
+            .into_iter()
+            .rev()
+            .filter(|coin| self.coins(*coin) > 0)
+            .map(|coin| format!("{} {}", self.coins(coin), coin))
+            .collect::<Vec<_>>();
+        if coins.is_empty() {
+            write!(f, "no coins")
+        } else {
+            write!(f, "{}", coins.join(" "))
+        }
+    }
+}
+
+/// Parses a number of coins typed into the sheet, an empty field being none
+pub fn parse_coins(input: &str) -> Result<u32> {
+    if input.is_empty() {
+        return Ok(0);
+    }
+    input.parse::<u32>().map_err(CharError::CoinsParseError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purse(cp: u32, sp: u32, ep: u32, gp: u32, pp: u32) -> Purse {
+        Purse {
+            cp,
+            sp,
+            ep,
+            gp,
+            pp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn value_weight_and_normalizing() {
+        let mut coins = purse(125, 12, 1, 3, 0);
+        assert_eq!(coins.value().unwrap(), 125 + 120 + 50 + 300);
+        assert_eq!(coins.weight(), 141.0 / 50.0);
+        assert_eq!(coins.to_string(), "3 gp 1 ep 12 sp 125 cp");
+        coins.normalize().unwrap();
+        assert_eq!(coins, purse(5, 4, 1, 0, 1));
+        assert_eq!(coins.to_string(), "1 pp 1 ep 4 sp 5 cp");
+        assert_eq!(Purse::default().to_string(), "no coins");
+        assert_eq!("ep".parse::<Coin>().unwrap(), Coin::Electrum);
+        assert!("zp".parse::<Coin>().is_err());
+    }
+
+    #[test]
+    fn exchanging_coins() {
+        let mut coins = purse(0, 15, 0, 0, 1);
+        assert_eq!(coins.exchange(Coin::Silver, 15, Coin::Gold).unwrap(), 1);
+        assert_eq!(coins, purse(0, 5, 0, 1, 1));
+        assert_eq!(coins.exchange(Coin::Platinum, 1, Coin::Silver).unwrap(), 50);
+        assert_eq!(coins.sp, 55);
+        match coins.exchange(Coin::Gold, 2, Coin::Copper) {
+            Err(CharError::NotEnoughCoins { held: 1, .. }) => (),
+            other => panic!("expected NotEnoughCoins, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn spending_makes_change() {
+        let mut coins = purse(5, 0, 0, 1, 0);
+        coins.spend(70).unwrap();
+        assert_eq!(coins, purse(5, 3, 0, 0, 0));
+        assert_eq!(coins.value().unwrap(), 35);
+        match coins.spend(36) {
+            Err(CharError::NotEnoughMoney {
+                cost: 36,
+                available: 35,
+            }) => (),
+            other => panic!("expected NotEnoughMoney, got {:?}", other),
+        }
+        coins.spend(35).unwrap();
+        assert_eq!(coins, Purse::default());
+    }
+
+    #[test]
+    fn purchases_are_logged() {
+        let mut coins = purse(0, 0, 0, 20, 0);
+        let torch = catalog::find("Torch").unwrap();
+        let sword = catalog::find("Long Sword").unwrap();
+        assert_eq!(
+            coins.buy(torch, 3).unwrap().to_string(),
+            "Bought Torch x3 for 3 cp, 19 gp 9 sp 7 cp left"
+        );
+        coins.buy(sword, 1).unwrap();
+        assert!(coins.buy(sword, 1).is_err());
+        assert_eq!(coins.transactions.len(), 2);
+        assert_eq!(coins.transactions[1].balance, 497);
+        assert_eq!(parse_coins("").unwrap(), 0);
+        assert!(parse_coins("lots").is_err());
+    }
+
+    #[test]
+    fn too_much_to_count() {
+        let mut hoard = purse(0, 0, 0, 0, u32::MAX);
+        match hoard.value() {
+            Err(CharError::ValueOverflow) => (),
+            other => panic!("expected ValueOverflow, got {:?}", other),
+        }
+        assert!(hoard.normalize().is_err());
+        assert!(hoard.spend(1).is_err());
+        assert!(hoard.exchange(Coin::Platinum, u32::MAX, Coin::Copper).is_err());
+        assert_eq!(hoard, purse(0, 0, 0, 0, u32::MAX));
+        assert_eq!(hoard.weight(), u32::MAX as f32 / 50.0);
+
+        let mut coppers = purse(u32::MAX, 1, 0, 0, 0);
+        assert!(coppers.exchange(Coin::Silver, 1, Coin::Copper).is_err());
+        assert_eq!(coppers, purse(u32::MAX, 1, 0, 0, 0));
+
+        let mut rich = purse(0, 0, 0, 0, 1_000);
+        let plate = catalog::find("Full Plate").unwrap();
+        match rich.buy(plate, u32::MAX) {
+            Err(CharError::InvalidQuantity(q)) => assert_eq!(q, u32::MAX),
+            other => panic!("expected InvalidQuantity, got {:?}", other),
+        }
+        assert!(rich.transactions.is_empty());
+
+        let gem = Valuable {
+            kind: ValuableKind::Gem,
+            description: "Star Ruby".to_string(),
+            value: u32::MAX,
+        };
+        rich.valuables = vec![gem.clone(), gem];
+        assert!(rich.valuables_value().is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::class::CharacterClass;
+use crate::common::{Ability, CharError};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Race {
+    #[default]
+    Human,
+    Dwarf,
+    Elf,
+    Gnome,
+    HalfElf,
+    Halfling,
+}
+
+impl FromStr for Race {
+    type Err = CharError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Human" => Ok(Race::Human),
+            "Dwarf" => Ok(Race::Dwarf),
+            "Elf" => Ok(Race::Elf),
+            "Gnome" => Ok(Race::Gnome),
+            "Half-Elf" => Ok(Race::HalfElf),
+            "Halfling" => Ok(Race::Halfling),
+            _ => Err(CharError::RaceParseError(String::from(s))),
+        }
+    }
+}
+
+impl Display for Race {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Race::Human => write!(f, "Human"),
+            Race::Dwarf => write!(f, "Dwarf"),
+            Race::Elf => write!(f, "Elf"),
+            Race::Gnome => write!(f, "Gnome"),
+            Race::HalfElf => write!(f, "Half-Elf"),
+            Race::Halfling => write!(f, "Halfling"),
+        }
+    }
+}
+
+impl Race {
+    /// Every race, in the order they are listed in the race select
+    pub fn all() -> Vec<Race> {
+        vec![
+            Race::Human,
+            Race::Dwarf,
+            Race::Elf,
+            Race::Gnome,
+            Race::HalfElf,
+            Race::Halfling,
+        ]
+    }
+
+    /// Racial ability adjustment, added to the rolled score
+    pub fn ability_adjustment(&self, ability: Ability) -> i32 {
+        match (self, ability) {
+            (Race::Dwarf, Ability::Con) => 1,
+            (Race::Dwarf, Ability::Cha) => -1,
+            (Race::Elf, Ability::Dex) => 1,
+            (Race::Elf, Ability::Con) => -1,
+            (Race::Gnome, Ability::Int) => 1,
+            (Race::Gnome, Ability::Wis) => -1,
+            (Race::Halfling, Ability::Dex) => 1,
+            (Race::Halfling, Ability::Str) => -1,
+            _ => 0,
+        }
+    }
+
+    /// Minimum and maximum rolled score the race allows in `ability`
+    pub fn ability_limits(&self, ability: Ability) -> (i32, i32) {
+        match (self, ability) {
+            (Race::Human, _) => (3, 18),
+            (Race::Dwarf, Ability::Str) => (8, 18),
+            (Race::Dwarf, Ability::Dex) => (3, 17),
+            (Race::Dwarf, Ability::Con) => (11, 18),
+            (Race::Dwarf, Ability::Cha) => (3, 17),
+            (Race::Elf, Ability::Dex) => (6, 18),
+            (Race::Elf, Ability::Con) => (7, 18),
+            (Race::Elf, Ability::Int) => (8, 18),
+            (Race::Elf, Ability::Cha) => (8, 18),
+            (Race::Gnome, Ability::Str) => (6, 18),
+            (Race::Gnome, Ability::Con) => (8, 18),
+            (Race::Gnome, Ability::Int) => (6, 18),
+            (Race::HalfElf, Ability::Dex) => (6, 18),
+            (Race::HalfElf, Ability::Con) => (6, 18),
+            (Race::HalfElf, Ability::Int) => (4, 18),
+            (Race::Halfling, Ability::Str) => (7, 18),
+            (Race::Halfling, Ability::Dex) => (7, 18),
+            (Race::Halfling, Ability::Con) => (10, 18),
+            (Race::Halfling, Ability::Int) => (6, 18),
+            (Race::Halfling, Ability::Wis) => (3, 17),
+            _ => (3, 18),
+        }
+    }
+
+    pub fn allows_class(&self, class: CharacterClass) -> bool {
+        use CharacterClass::*;
+        match self {
+            Race::Human => true,
+            Race::Dwarf | Race::Halfling => [Fighter, Cleric, Thief].contains(&class),
+            Race::Elf => [Fighter, Ranger, Mage, Diviner, Enchanter, Cleric, Thief].contains(&class),
+            Race::Gnome => [Fighter, Illusionist, Cleric, Thief].contains(&class),
+            Race::HalfElf => [
+                Fighter, Ranger, Mage, Conjurer, Diviner, Enchanter, Transmuter, Cleric, Druid,
+                Thief, Bard,
+            ]
+            .contains(&class),
+        }
+    }
+
+    /// Class combinations the race may take as a multi-class character. Humans can't multi-class.
+    pub fn multi_classes(&self) -> Vec<Vec<CharacterClass>> {
+        use CharacterClass::*;
+        match self {
+            Race::Human => vec![],
+            Race::Dwarf => vec![vec![Fighter, Cleric], vec![Fighter, Thief]],
+            Race::Elf => vec![
+                vec![Fighter, Mage],
+                vec![Fighter, Thief],
+                vec![Mage, Thief],
+                vec![Fighter, Mage, Thief],
+            ],
+            Race::Gnome => vec![
+                vec![Fighter, Cleric],
+                vec![Fighter, Illusionist],
+                vec![Fighter, Thief],
+                vec![Cleric, Illusionist],
+                vec![Cleric, Thief],
+                vec![Illusionist, Thief],
+            ],
+            Race::HalfElf => vec![
+                vec![Fighter, Cleric],
+                vec![Fighter, Druid],
+                vec![Fighter, Mage],
+                vec![Fighter, Thief],
+                vec![Fighter, Mage, Cleric],
+                vec![Fighter, Mage, Druid],
+                vec![Fighter, Mage, Thief],
+                vec![Cleric, Ranger],
+                vec![Druid, Ranger],
+                vec![Cleric, Mage],
+                vec![Druid, Mage],
+                vec![Mage, Thief],
+            ],
+            Race::Halfling => vec![vec![Fighter, Thief]],
+        }
+    }
+
+    /// Infravision range in feet, if any
+    pub fn infravision(&self) -> Option<i32> {
+        match self {
+            Race::Human | Race::Halfling => None,
+            Race::Dwarf | Race::Elf | Race::Gnome | Race::HalfElf => Some(60),
+        }
+    }
+
+    /// Movement rate when unencumbered, in tens of yards per round outdoors
+    pub fn base_movement(&self) -> i32 {
+        match self {
+            Race::Human | Race::Elf | Race::HalfElf => 12,
+            Race::Dwarf | Race::Gnome | Race::Halfling => 6,
+        }
+    }
+
+    /// Saving throw bonuses for a short, hardy race with Constitution `con`: first against rods,
+    /// staves, wands and spells, then against poison
+    pub fn save_bonus(&self, con: i32) -> (i32, i32) {
+        let bonus = match con {
+            4..=6 => 1,
+            7..=10 => 2,
+            11..=13 => 3,
+            14..=17 => 4,
+            18..=25 => 5,
+            _ => 0,
+        };
+        match self {
+            Race::Dwarf | Race::Halfling => (bonus, bonus),
+            Race::Gnome => (bonus, 0),
+            Race::Human | Race::Elf | Race::HalfElf => (0, 0),
+        }
+    }
+
+    pub fn special_abilities(&self) -> Vec<&'static str> {
+        match self {
+            Race::Human => vec![],
+            Race::Dwarf => vec![
+                "save bonus vs magic and poison based on Con",
+                "+1 to hit orcs, half-orcs, goblins and hobgoblins",
+                "-4 AC vs ogres, trolls, ogre magi, giants and titans",
+                "detect grade or slope (1-5 on d6)",
+                "detect new construction (1-5 on d6)",
+                "detect sliding or shifting walls (1-4 on d6)",
+                "detect stonework traps (1-3 on d6)",
+                "determine depth underground (1-3 on d6)",
+            ],
+            Race::Elf => vec![
+                "90% resistance to sleep and charm",
+                "+1 to hit with bows and long or short swords",
+                "find secret doors (1 on d6 passing, 1-2 searching)",
+                "opponents -4 to surprise rolls when alone or with elves",
+            ],
+            Race::Gnome => vec![
+                "save bonus vs magic based on Con",
+                "20% chance for non-weapon magic items to malfunction",
+                "+1 to hit kobolds and goblins",
+                "-4 AC vs gnolls, bugbears, ogres, trolls, ogre magi, giants and titans",
+                "detect grade or slope (1-5 on d6)",
+                "detect unsafe walls, ceilings and floors (1-7 on d10)",
+                "determine depth underground (1-4 on d6)",
+                "determine direction underground (1-3 on d6)",
+            ],
+            Race::HalfElf => vec![
+                "30% resistance to sleep and charm",
+                "find secret doors (1 on d6 passing, 1-2 searching)",
+            ],
+            Race::Halfling => vec![
+                "save bonus vs magic and poison based on Con",
+                "+1 to hit with slings and thrown weapons",
+                "opponents -4 to surprise rolls when alone or with halflings",
+                "stout: 30' infravision and detect grade or slope (75%)",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn race_round_trip() {
+        for race in Race::all() {
+            assert_eq!(race.to_string().parse::<Race>().unwrap(), race);
+            assert!(race.allows_class(CharacterClass::Fighter));
+            for classes in race.multi_classes() {
+                assert!(classes.iter().all(|cc| race.allows_class(*cc)));
+            }
+        }
+    }
+
+    #[test]
+    fn hardy_save_bonus() {
+        assert_eq!(Race::Dwarf.save_bonus(17), (4, 4));
+        assert_eq!(Race::Halfling.save_bonus(10), (2, 2));
+        assert_eq!(Race::Gnome.save_bonus(18), (5, 0));
+        assert_eq!(Race::Elf.save_bonus(18), (0, 0));
+        assert_eq!(Race::Dwarf.save_bonus(3), (0, 0));
+    }
+}
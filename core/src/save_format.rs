@@ -0,0 +1,160 @@
+use crate::character::Character;
+use crate::common::{CharError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version written by `to_json`. Bump it and add a migration to `MIGRATIONS` whenever a change to
+/// `Character` would stop older documents from loading.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Upgrades the character in a document of version `n` to version `n + 1`
+type Migration = fn(Value) -> Result<Value>;
+
+/// `MIGRATIONS[n]` upgrades version `n` to `n + 1`
+const MIGRATIONS: &[Migration] = &[v0_to_v1];
+
+/// What every saved or exported character is wrapped in
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Envelope<T> {
+    version: u32,
+    character: T,
+}
+
+fn migration_error(path: &str, reason: &str) -> CharError {
+    CharError::ImportError {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Version 0 is the bare character JSON written before there was an envelope. The first sheets
+/// only knew the four class groups and had no race.
+fn v0_to_v1(mut character: Value) -> Result<Value> {
+    let fields = character
+        .as_object_mut()
+        .ok_or_else(|| migration_error(".", "expected a character object"))?;
+    if let Some(class) = fields.get_mut("char_class") {
+        let renamed = match class.as_str() {
+            Some("Wizard") => Some("Mage"),
+            Some("Rogue") => Some("Thief"),
+            _ => None,
+        };
+        if let Some(renamed) = renamed {
+            *class = Value::from(renamed);
+        }
+    }
+    fields
+        .entry("race")
+        .or_insert_with(|| Value::from("Human"));
+    Ok(character)
+}
+
+/// Serializes `character` in the current versioned format
+pub fn to_json(character: &Character) -> Result<String> {
+    serde_json::to_string_pretty(&Envelope {
+        version: CURRENT_VERSION,
+        character,
+    })
+    .map_err(|e| CharError::StorageError(e.to_string()))
+}
+
+/// Unwraps a saved document and runs it through every migration between its version and the
+/// current one, returning the character in the current format. Documents without an envelope
+/// are version 0.
+pub fn upgrade(document: Value) -> Result<Value> {
+    let is_envelope = document
+        .as_object()
+        .map(|fields| fields.contains_key("version"))
+        .unwrap_or(false);
+    let (version, mut character) = if is_envelope {
+        let envelope: Envelope<Value> = serde_json::from_value(document)
+            .map_err(|e| migration_error(".", &e.to_string()))?;
+        (envelope.version, envelope.character)
+    } else {
+        (0, document)
+    };
+
+    if version > CURRENT_VERSION {
+        return Err(CharError::UnsupportedVersion(version));
+    }
+    for migration in &MIGRATIONS[version as usize..] {
+        character = migration(character)?;
+    }
+    Ok(character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::class::CharacterClass;
+    use crate::race::Race;
+
+    fn load(json: &str) -> Character {
+        let (character, errors) = Character::from_json_str(json).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        character
+    }
+
+    #[test]
+    fn migrations_cover_every_version() {
+        assert_eq!(MIGRATIONS.len(), CURRENT_VERSION as usize);
+    }
+
+    #[test]
+    fn loads_v0_class_groups() {
+        let character = load(include_str!("../tests/fixtures/v0_class_groups.json"));
+        assert_eq!(character.char_name, "Elmo");
+        assert_eq!(character.char_class, CharacterClass::Mage);
+        assert_eq!(character.race, Race::Human);
+        assert_eq!(character.str_percentile, None);
+        assert_eq!(character.int, 17);
+    }
+
+    #[test]
+    fn loads_v0_with_race() {
+        let character = load(include_str!("../tests/fixtures/v0_with_race.json"));
+        assert_eq!(character.char_class, CharacterClass::Thief);
+        assert_eq!(character.race, Race::Halfling);
+        assert_eq!(character.dex, 16);
+    }
+
+    #[test]
+    fn loads_v0_wizard_with_race() {
+        let character = load(include_str!("../tests/fixtures/v0_wizard_with_race.json"));
+        assert_eq!(character.char_class, CharacterClass::Mage);
+        assert_eq!(character.race, Race::Elf);
+        assert_eq!(character.int, 16);
+    }
+
+    #[test]
+    fn loads_v1() {
+        let character = load(include_str!("../tests/fixtures/v1.json"));
+        assert_eq!(character.char_class, CharacterClass::Paladin);
+        assert_eq!(character.str_percentile, Some(76));
+        assert_eq!(character.cha, 17);
+    }
+
+    #[test]
+    fn round_trips_current_version() {
+        let character = load(include_str!("../tests/fixtures/v1.json"));
+        let json = to_json(&character).unwrap();
+        assert!(json.contains(&format!("\"version\": {}", CURRENT_VERSION)));
+        assert_eq!(to_json(&load(&json)).unwrap(), json);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let json = format!(
+            r#"{{"version": {}, "character": {{}}}}"#,
+            CURRENT_VERSION + 1
+        );
+        match Character::from_json_str(&json) {
+            Err(errors) => match errors[0] {
+                CharError::UnsupportedVersion(v) => assert_eq!(v, CURRENT_VERSION + 1),
+                ref other => panic!("expected UnsupportedVersion, got {:?}", other),
+            },
+            Ok(_) => panic!("expected a newer version to be rejected"),
+        }
+    }
+}
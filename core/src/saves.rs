@@ -0,0 +1,140 @@
+use crate::class::ClassGroup;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveCategory {
+    ParalyzationPoisonDeath,
+    RodStaffWand,
+    PetrificationPolymorph,
+    BreathWeapon,
+    Spell,
+}
+
+impl Display for SaveCategory {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            SaveCategory::ParalyzationPoisonDeath => write!(f, "Paralyzation/Poison/Death"),
+            SaveCategory::RodStaffWand => write!(f, "Rod/Staff/Wand"),
+            SaveCategory::PetrificationPolymorph => write!(f, "Petrification/Polymorph"),
+            SaveCategory::BreathWeapon => write!(f, "Breath Weapon"),
+            SaveCategory::Spell => write!(f, "Spell"),
+        }
+    }
+}
+
+impl SaveCategory {
+    /// In the order of the saving throw tables
+    pub fn all() -> Vec<SaveCategory> {
+        vec![
+            SaveCategory::ParalyzationPoisonDeath,
+            SaveCategory::RodStaffWand,
+            SaveCategory::PetrificationPolymorph,
+            SaveCategory::BreathWeapon,
+            SaveCategory::Spell,
+        ]
+    }
+}
+
+/// Every category's save for a group at `level`, in the order of `SaveCategory::all()`
+pub fn base_saves(group: ClassGroup, level: u32) -> [i32; 5] {
+    let level = level.max(1);
+    match group {
+        ClassGroup::Warrior => match level {
+            1 | 2 => [14, 16, 15, 17, 17],
+            3 | 4 => [13, 15, 14, 16, 16],
+            5 | 6 => [11, 13, 12, 13, 14],
+            7 | 8 => [10, 12, 11, 12, 13],
+            9 | 10 => [8, 10, 9, 9, 11],
+            11 | 12 => [7, 9, 8, 8, 10],
+            13 | 14 => [5, 7, 6, 5, 8],
+            15 | 16 => [4, 6, 5, 4, 7],
+            _ => [3, 5, 4, 4, 6],
+        },
+        ClassGroup::Priest => match level {
+            1..=3 => [10, 14, 13, 16, 15],
+            4..=6 => [9, 13, 12, 15, 14],
+            7..=9 => [7, 11, 10, 13, 12],
+            10..=12 => [6, 10, 9, 12, 11],
+            13..=15 => [5, 9, 8, 11, 10],
+            16..=18 => [4, 8, 7, 10, 9],
+            _ => [2, 6, 5, 8, 7],
+        },
+        ClassGroup::Rogue => match level {
+            1..=4 => [13, 14, 12, 16, 15],
+            5..=8 => [12, 12, 11, 15, 13],
+            9..=12 => [11, 10, 10, 14, 11],
+            13..=16 => [10, 8, 9, 13, 9],
+            17..=20 => [9, 6, 8, 12, 7],
+            _ => [8, 4, 7, 11, 5],
+        },
+        ClassGroup::Wizard => match level {
+            1..=5 => [14, 11, 13, 15, 12],
+            6..=10 => [13, 9, 11, 13, 10],
+            11..=15 => [11, 7, 9, 11, 8],
+            16..=20 => [10, 5, 7, 9, 6],
+            _ => [8, 3, 5, 7, 4],
+        },
+    }
+}
+
+/// The number to roll on a d20 to save against one category
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavingThrow {
+    pub category: SaveCategory,
+    /// With the bonuses that always apply already taken off
+    pub target: i32,
+    /// Bonuses that only count against some attacks, e.g. `("poison", 4)`
+    pub situational: Vec<(&'static str, i32)>,
+}
+
+/// e.g. `13 (+4 vs poison)`
+impl Display for SavingThrow {
+    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{}", self.target)?;
+        if !self.situational.is_empty() {
+            let bonuses = self
+                .situational
+                .iter()
+                .map(|(against, bonus)| format!("{:+} vs {}", bonus, against))
+                .collect::<Vec<_>>();
+            write!(f, " ({})", bonuses.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_by_group_and_level() {
+        assert_eq!(base_saves(ClassGroup::Warrior, 1), [14, 16, 15, 17, 17]);
+        assert_eq!(base_saves(ClassGroup::Warrior, 17), [3, 5, 4, 4, 6]);
+        assert_eq!(base_saves(ClassGroup::Priest, 19), [2, 6, 5, 8, 7]);
+        assert_eq!(base_saves(ClassGroup::Rogue, 9), [11, 10, 10, 14, 11]);
+        assert_eq!(base_saves(ClassGroup::Wizard, 5), [14, 11, 13, 15, 12]);
+        // every save gets better or stays the same as levels go up
+        for group in &[
+            ClassGroup::Warrior,
+            ClassGroup::Wizard,
+            ClassGroup::Priest,
+            ClassGroup::Rogue,
+        ] {
+            for level in 1..21 {
+                let (now, next) = (base_saves(*group, level), base_saves(*group, level + 1));
+                assert!(now.iter().zip(next.iter()).all(|(a, b)| b <= a));
+            }
+        }
+    }
+
+    #[test]
+    fn situational_bonuses_are_shown() {
+        let save = SavingThrow {
+            category: SaveCategory::ParalyzationPoisonDeath,
+            target: 13,
+            situational: vec![("poison", 4)],
+        };
+        assert_eq!(save.to_string(), "13 (+4 vs poison)");
+    }
+}
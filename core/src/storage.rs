@@ -0,0 +1,226 @@
+use crate::character::Character;
+use crate::common::{CharError, Result};
+use crate::save_format;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const ROSTER_KEY: &str = "charsheet.roster";
+
+fn character_key(id: u32) -> String {
+    format!("charsheet.character.{}", id)
+}
+
+/// A string key/value store characters can be saved to
+pub trait Storage {
+    fn get_item(&self, key: &str) -> Option<String>;
+    fn set_item(&mut self, key: &str, value: &str) -> Result<()>;
+    fn remove_item(&mut self, key: &str) -> Result<()>;
+}
+
+/// Keeps everything in memory, for tools and tests that run outside the browser
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    items: HashMap<String, String>,
+}
+
+impl Storage for MemoryStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        self.items.get(key).cloned()
+    }
+
+    fn set_item(&mut self, key: &str, value: &str) -> Result<()> {
+        self.items.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove_item(&mut self, key: &str) -> Result<()> {
+        self.items.remove(key);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RosterEntry {
+    pub id: u32,
+    pub name: String,
+}
+
+/// The list of saved characters. Each character is stored under its own key, the roster itself
+/// only keeps ids and names so it can be listed without loading every character.
+pub struct Roster<S: Storage> {
+    storage: S,
+}
+
+impl<S: Storage> Roster<S> {
+    pub fn new(storage: S) -> Self {
+        Roster { storage }
+    }
+
+    /// The saved characters. A roster that can't be read is an error rather than an empty
+    /// list, so new characters never get saved over ones it still lists.
+    pub fn entries(&self) -> Result<Vec<RosterEntry>> {
+        match self.storage.get_item(ROSTER_KEY) {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| CharError::StorageError(format!("unreadable roster: {}", e))),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn set_entries(&mut self, entries: &[RosterEntry]) -> Result<()> {
+        let json = serde_json::to_string(entries)
+            .map_err(|e| CharError::StorageError(e.to_string()))?;
+        self.storage.set_item(ROSTER_KEY, &json)
+    }
+
+    pub fn load(&self, id: u32) -> Result<Character> {
+        let json = self
+            .storage
+            .get_item(&character_key(id))
+            .ok_or_else(|| CharError::StorageError(format!("no saved character {}", id)))?;
+        let (mut character, _) = Character::from_json_str(&json).map_err(|errors| {
+            CharError::StorageError(
+                errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })?;
+        character.save_id = Some(id);
+        Ok(character)
+    }
+
+    /// Saves `character` under `id`, adding it to the roster if it isn't there yet
+    pub fn save(&mut self, id: u32, character: &Character) -> Result<()> {
+        let mut entries = self.entries()?;
+        let json = save_format::to_json(character)?;
+        self.storage.set_item(&character_key(id), &json)?;
+
+        match entries.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => entry.name = character.char_name.clone(),
+            None => entries.push(RosterEntry {
+                id,
+                name: character.char_name.clone(),
+            }),
+        }
+        self.set_entries(&entries)
+    }
+
+    fn next_id(&self) -> Result<u32> {
+        Ok(self
+            .entries()?
+            .iter()
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(1))
+    }
+
+    /// Saves a new blank character and returns its id
+    pub fn create(&mut self) -> Result<u32> {
+        let id = self.next_id()?;
+        self.save(id, &Character::default())?;
+        Ok(id)
+    }
+
+    /// Id of the first saved character, saving a new one if the roster is empty
+    pub fn first_or_create(&mut self) -> Result<u32> {
+        match self.entries()?.first() {
+            Some(entry) => Ok(entry.id),
+            None => self.create(),
+        }
+    }
+
+    /// Saves a copy of the character `id` and returns the id of the copy
+    pub fn duplicate(&mut self, id: u32) -> Result<u32> {
+        let mut character = self.load(id)?;
+        character.char_name = format!("{} (copy)", character.char_name);
+        let copy = self.next_id()?;
+        self.save(copy, &character)?;
+        Ok(copy)
+    }
+
+    pub fn delete(&mut self, id: u32) -> Result<()> {
+        let entries = self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.id != id)
+            .collect::<Vec<_>>();
+        self.storage.remove_item(&character_key(id))?;
+        self.set_entries(&entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_save_load() {
+        let mut roster = Roster::new(MemoryStorage::default());
+        assert!(roster.entries().unwrap().is_empty());
+
+        let id = roster.create().unwrap();
+        let mut character = roster.load(id).unwrap();
+        assert_eq!(character.save_id, Some(id));
+        character.char_name = "Mialee".to_string();
+        character.int = 17;
+        roster.save(id, &character).unwrap();
+
+        assert_eq!(
+            roster.entries().unwrap(),
+            vec![RosterEntry {
+                id,
+                name: "Mialee".to_string()
+            }]
+        );
+        assert_eq!(roster.load(id).unwrap().int, 17);
+    }
+
+    #[test]
+    fn duplicate_and_delete() {
+        let mut roster = Roster::new(MemoryStorage::default());
+        let id = roster.create().unwrap();
+        let mut character = roster.load(id).unwrap();
+        character.char_name = "Lidda".to_string();
+        roster.save(id, &character).unwrap();
+
+        let copy = roster.duplicate(id).unwrap();
+        assert_ne!(copy, id);
+        assert_eq!(roster.load(copy).unwrap().char_name, "Lidda (copy)");
+        assert_eq!(roster.entries().unwrap().len(), 2);
+
+        roster.delete(id).unwrap();
+        assert!(roster.load(id).is_err());
+        assert_eq!(
+            roster.entries().unwrap(),
+            vec![RosterEntry {
+                id: copy,
+                name: "Lidda (copy)".to_string()
+            }]
+        );
+        // ids aren't reused while the roster still has later entries
+        assert!(roster.create().unwrap() > copy);
+    }
+
+    #[test]
+    fn corrupt_roster_is_left_alone() {
+        let mut roster = Roster::new(MemoryStorage::default());
+        let id = roster.create().unwrap();
+        let mut character = roster.load(id).unwrap();
+        character.char_name = "Regdar".to_string();
+        roster.save(id, &character).unwrap();
+        roster.storage.set_item(ROSTER_KEY, "[{\"id\": 1,").unwrap();
+
+        match roster.entries() {
+            Err(CharError::StorageError(_)) => (),
+            other => panic!("expected StorageError, got {:?}", other),
+        }
+        assert!(roster.first_or_create().is_err());
+        assert!(roster.create().is_err());
+        assert!(roster.duplicate(id).is_err());
+        assert!(roster.save(id, &Character::default()).is_err());
+        assert!(roster.delete(id).is_err());
+        // the saved character is still there
+        assert_eq!(roster.load(id).unwrap().char_name, "Regdar");
+    }
+}
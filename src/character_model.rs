@@ -1,18 +1,40 @@
-use crate::common::CharError;
-use crate::common::CharError::{InvalidPercentile, InvalidStrength};
-use crate::common::Result;
+use crate::rng::CryptoRng;
+use crate::storage::LocalStorage;
 use crate::utils;
-use crate::utils::{build_form_field_input, build_form_field_select, input_error_handler};
+use crate::utils::{
+    build_derived_scores, build_form_field_input, build_form_field_select, input_error_handler,
+    select_error_handler, set_input_value, set_select_options, set_select_value,
+    set_textarea_value,
+};
+use charsheet_core::abilities::{
+    Charisma, Constitution, Dexterity, Intelligence, StrengthPercentile, Wisdom,
+};
+use charsheet_core::armor_class::{self, ArmorClass, ArmorType, Protection, ShieldType};
+use charsheet_core::catalog::{self, CATALOG};
+use charsheet_core::character::Character;
+use charsheet_core::class::CharacterClass;
+use charsheet_core::combat::{Attack, WeaponAttack, WeaponSkill};
+use charsheet_core::common::{Ability, CharError, Result};
+use charsheet_core::dice::{self, Roll};
+use charsheet_core::encumbrance::Encumbrance;
+use charsheet_core::generation::{Generated, GenerationMethod};
+use charsheet_core::hit_points::{self, HitPoints};
+use charsheet_core::inventory::{self, Inventory, Location};
+use charsheet_core::level::{self, LevelUp};
+use charsheet_core::proficiency::{Proficiencies, WEAPON_GROUPS};
+use charsheet_core::purse::{self, Coin, Purse, Valuable, ValuableKind};
+use charsheet_core::race::Race;
+use charsheet_core::save_format;
+use charsheet_core::saves::{SaveCategory, SavingThrow};
+use charsheet_core::storage::{Roster, RosterEntry};
 use log::{error, info};
 use mogwai::prelude::*;
-use serde::Serialize;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub enum In {
     CharName(String),
     CharClass(String),
+    Race(String),
     Str(String),
     StrPercentile(String),
     Dex(String),
@@ -20,257 +42,376 @@ pub enum In {
     Int(String),
     Wis(String),
     Cha(String),
+    Import(String),
+    Startup,
+    Open(String),
+    New,
+    Duplicate,
+    Delete,
+    Roll(String),
+    Generate(String),
+    Assign(usize, String),
+    UseScores,
+    RollHitPoints,
+    Damage(String),
+    Heal(String),
+    TempHp(String),
+    SetXp(String),
+    AwardXp(String),
+    Armor(String),
+    ArmorBonus(String),
+    Shield(String),
+    ShieldBonus(String),
+    Rings(String),
+    Cloak(String),
+    /// Catalog name, quantity and location
+    AddItem(String, String, String),
+    /// Inventory index and quantity, all of the stack when the quantity is empty
+    RemoveItem(String, String),
+    /// Inventory index and location
+    MoveItem(String, String),
+    /// Catalog name, quantity and location, paid for from the purse
+    BuyItem(String, String, String),
+    Coins(Coin, String),
+    NormalizeCoins,
+    /// Kind, description and value in gold pieces
+    AddValuable(String, String, String),
+    /// Index in the purse's valuables
+    RemoveValuable(String),
+    /// Weapon or weapon group name
+    LearnWeapon(String),
+    ForgetWeapon(String),
+    Specialize(String),
 }
 
 #[derive(Debug, Clone)]
 pub enum Out {
     StrPercentile(StrengthPercentile),
     CharClass(CharacterClass),
-    Dex(Option<i32>),
-    Con(Option<i32>),
-    Int(Option<i32>),
-    Wis(Option<i32>),
-    Cha(Option<i32>),
+    ClassAllowed(Option<CharError>),
+    Race(Race),
+    Dex(Dexterity),
+    Con(Constitution),
+    Int(Intelligence),
+    Wis(Wisdom),
+    Cha(Charisma),
     JsonRender(Option<String>),
+    Loaded(Character),
+    Roster(Vec<RosterEntry>, Option<u32>),
+    ImportErrors(Vec<CharError>),
+    Rolled(Result<Roll>),
+    Generated(Generated, Vec<Ability>, Option<CharError>),
+    HitPoints {
+        current: i32,
+        max: i32,
+        hit_points: HitPoints,
+        err: Option<CharError>,
+    },
+    Level {
+        level: u32,
+        xp: u32,
+        next_level_xp: Option<u32>,
+        xp_bonus: bool,
+        hit_dice_owed: usize,
+        err: Option<CharError>,
+    },
+    LevelUp(LevelUp),
+    Combat {
+        thac0: i32,
+        melee: Attack,
+        missile: Attack,
+        non_proficiency_penalty: i32,
+        proficiencies: Proficiencies,
+        weapon_slots: u32,
+        weapon_slots_used: u32,
+        weapons: Vec<WeaponAttack>,
+    },
+    /// Whether the last change to the weapon proficiencies worked
+    ProficiencyChange(Option<CharError>),
+    SavingThrows(Vec<SavingThrow>),
+    ArmorClass(ArmorClass),
+    /// Whether the protection field with the id could be read
+    ProtectionInput(&'static str, Option<CharError>),
+    Inventory(Inventory, Option<CharError>),
+    Protection(Protection),
+    Purse(Purse),
+    /// Whether the purse field with the id could be read
+    PurseInput(&'static str, Option<CharError>),
+    Encumbrance {
+        load: f32,
+        encumbrance: Encumbrance,
+        movement: i32,
+    },
 }
 
-#[derive(Debug, Clone)]
-pub struct StrengthPercentile {
-    str: i32,
-    per: Option<i32>,
-    err: Option<CharError>,
+/// The character being edited, as a mogwai component
+#[derive(Debug, Clone, Default)]
+pub struct CharacterSheet {
+    character: Character,
+    /// Rolled ability scores and the ability picked for each, until they are used
+    generated: Option<(Generated, Vec<Ability>)>,
 }
 
-impl StrengthPercentile {
-    fn hit_adj(&self) -> Result<i32> {
-        match self.str {
-            1 => Ok(-5),
-            2 | 3 => Ok(-3),
-            4 | 5 => Ok(-2),
-            6 | 7 => Ok(-1),
-            8..=16 => Ok(0),
-            17 => Ok(1),
-            18 => {
-                if let Some(p) = self.per {
-                    match p {
-                        1..=50 => Ok(1),
-                        51..=99 => Ok(2),
-                        100 => Ok(3),
-                        err => Err(CharError::InvalidPercentile(Some(err))),
-                    }
-                } else {
-                    Ok(1)
-                }
+impl CharacterSheet {
+    fn to_json_string(&self) -> Option<String> {
+        save_format::to_json(&self.character).ok()
+    }
+
+    /// Replaces this character with `character` and refreshes the whole sheet
+    fn load(&mut self, character: Character, tx_view: &Transmitter<Out>) {
+        self.character = character;
+        tx_view.send(&Out::Loaded(self.character.clone()));
+        tx_view.send(&Out::Race(self.character.race));
+        tx_view.send(&Out::CharClass(self.character.char_class));
+        tx_view.send(&Out::ClassAllowed(self.character.validate_class().err()));
+        self.revalidate_abilities(tx_view);
+        tx_view.send(&Out::Inventory(self.character.inventory.clone(), None));
+        tx_view.send(&Out::Purse(self.character.purse.clone()));
+    }
+
+    /// Runs `pick` against the saved roster and switches to the character whose id it returns
+    fn open_saved<F>(&mut self, tx_view: &Transmitter<Out>, pick: F)
+    where
+        F: FnOnce(&mut Roster<LocalStorage>, Option<u32>) -> Result<u32>,
+    {
+        let opened = LocalStorage::open().map(Roster::new).and_then(|mut roster| {
+            let id = pick(&mut roster, self.character.save_id)?;
+            Ok((roster.load(id)?, roster.entries()?))
+        });
+        match opened {
+            Ok((character, entries)) => {
+                info!("opened saved character {:?}", character.save_id);
+                tx_view.send(&Out::Roster(entries, character.save_id));
+                self.load(character, tx_view);
             }
-            19 | 20 => Ok(3),
-            21 | 22 => Ok(4),
-            23 => Ok(5),
-            24 => Ok(6),
-            25 => Ok(7),
-            err => Err(CharError::InvalidStrength(err)),
+            Err(e) => error!("couldn't open saved character: {}", e),
         }
     }
-    fn damage_adj(&self) -> Result<i32> {
-        match self.str {
-            1 => Ok(-4),
-            2 => Ok(-2),
-            3 => Ok(-1),
-            4 | 5 => Ok(-1),
-            6 | 7 => Ok(0),
-            8 | 9 => Ok(0),
-            10 | 11 => Ok(0),
-            12 | 13 => Ok(0),
-            14 | 15 => Ok(0),
-            16 => Ok(1),
-            17 => Ok(1),
-            18 => {
-                if let Some(p) = self.per {
-                    match p {
-                        1..=50 => Ok(3),
-                        51..=75 => Ok(3),
-                        76..=90 => Ok(4),
-                        91..=99 => Ok(5),
-                        100 => Ok(6),
-                        i => Err(InvalidPercentile(Some(i))),
-                    }
-                } else {
-                    Ok(2)
-                }
-            }
-            19 => Ok(7),
-            20 => Ok(8),
-            21 => Ok(9),
-            22 => Ok(10),
-            23 => Ok(11),
-            24 => Ok(12),
-            25 => Ok(14),
-            i => Err(InvalidStrength(i)),
+
+    /// Writes the character back to its slot in the roster
+    fn save(&self) -> Result<()> {
+        if let Some(id) = self.character.save_id {
+            Roster::new(LocalStorage::open()?).save(id, &self.character)?;
         }
+        Ok(())
     }
-    fn weight_allow(&self) -> Result<i32> {
-        match self.str {
-            1 => Ok(1),
-            2 => Ok(1),
-            3 => Ok(5),
-            4 | 5 => Ok(10),
-            6 | 7 => Ok(20),
-            8 | 9 => Ok(35),
-            10 | 11 => Ok(40),
-            12 | 13 => Ok(45),
-            14 | 15 => Ok(55),
-            16 => Ok(70),
-            17 => Ok(85),
-            18 => {
-                if let Some(p) = self.per {
-                    match p {
-                        1..=50 => Ok(135),
-                        51..=75 => Ok(160),
-                        76..=90 => Ok(185),
-                        91..=99 => Ok(235),
-                        100 => Ok(335),
-                        i => Err(InvalidPercentile(Some(i))),
-                    }
-                } else {
-                    Ok(110)
-                }
-            }
-            19 => Ok(485),
-            20 => Ok(535),
-            21 => Ok(635),
-            22 => Ok(785),
-            23 => Ok(935),
-            24 => Ok(1235),
-            25 => Ok(1535),
-            i => Err(InvalidStrength(i)),
+
+    /// Builds the view message carrying the derived table for `ability`
+    fn ability_out(&self, ability: Ability, err: Option<CharError>) -> Out {
+        let character = &self.character;
+        match ability {
+            Ability::Str => Out::StrPercentile(StrengthPercentile {
+                err,
+                ..character.strength()
+            }),
+            Ability::Dex => Out::Dex(Dexterity {
+                err,
+                ..character.dexterity()
+            }),
+            Ability::Con => Out::Con(Constitution {
+                err,
+                ..character.constitution()
+            }),
+            Ability::Int => Out::Int(Intelligence {
+                err,
+                ..character.intelligence()
+            }),
+            Ability::Wis => Out::Wis(Wisdom {
+                err,
+                ..character.wisdom()
+            }),
+            Ability::Cha => Out::Cha(Charisma {
+                err,
+                ..character.charisma()
+            }),
         }
     }
-    fn max_press(&self) -> Result<i32> {
-        match self.str {
-            1 => Ok(3),
-            2 => Ok(5),
-            3 => Ok(10),
-            4 | 5 => Ok(25),
-            6 | 7 => Ok(55),
-            8 | 9 => Ok(90),
-            10 | 11 => Ok(115),
-            12 | 13 => Ok(140),
-            14 | 15 => Ok(170),
-            16 => Ok(195),
-            17 => Ok(220),
-            18 => {
-                if let Some(p) = self.per {
-                    match p {
-                        1..=50 => Ok(280),
-                        51..=75 => Ok(305),
-                        76..=90 => Ok(330),
-                        91..=99 => Ok(380),
-                        100 => Ok(480),
-                        i => Err(InvalidPercentile(Some(i))),
-                    }
-                } else {
-                    Ok(255)
-                }
+
+    /// Re-checks every score that has been entered, e.g. after the race or class changed
+    fn revalidate_abilities(&self, tx_view: &Transmitter<Out>) {
+        for ability in Ability::all() {
+            if self.character.ability(ability) != 0 {
+                let err = self.character.validate_ability(ability).err();
+                tx_view.send(&self.ability_out(ability, err));
             }
-            19 => Ok(640),
-            20 => Ok(700),
-            21 => Ok(810),
-            22 => Ok(970),
-            23 => Ok(1130),
-            24 => Ok(1440),
-            25 => Ok(1750),
-            i => Err(InvalidStrength(i)),
         }
     }
-    fn open_doors(&self) -> Result<i32> {
-        match self.str {
-            1 => Ok(3),
-            2 => Ok(5),
-            3 => Ok(10),
-            4 | 5 => Ok(25),
-            6 | 7 => Ok(55),
-            8 | 9 => Ok(90),
-            10 | 11 => Ok(115),
-            12 | 13 => Ok(140),
-            14 | 15 => Ok(170),
-            16 => Ok(195),
-            17 => Ok(220),
-            18 => {
-                if let Some(p) = self.per {
-                    match p {
-                        1..=50 => Ok(280),
-                        51..=75 => Ok(305),
-                        76..=90 => Ok(330),
-                        91..=99 => Ok(380),
-                        100 => Ok(480),
-                        i => Err(InvalidPercentile(Some(i))),
-                    }
-                } else {
-                    Ok(255)
-                }
+
+    /// Applies a damage, healing or temporary hit points amount typed into the sheet
+    fn update_hit_points<F>(&mut self, input: &str, tx_view: &Transmitter<Out>, change: F)
+    where
+        F: FnOnce(&mut HitPoints, i32),
+    {
+        let changed = hit_points::parse_amount(input)
+            .map(|amount| change(&mut self.character.hit_points, amount));
+        self.hit_points_out(changed.err(), tx_view);
+    }
+
+    fn hit_points_out(&self, err: Option<CharError>, tx_view: &Transmitter<Out>) {
+        tx_view.send(&Out::HitPoints {
+            current: self.character.current_hp(),
+            max: self.character.max_hp(),
+            hit_points: self.character.hit_points.clone(),
+            err,
+        });
+    }
+
+    /// Applies an experience amount typed into the sheet, announcing any level gained
+    fn update_xp<F>(&mut self, input: &str, tx_view: &Transmitter<Out>, change: F)
+    where
+        F: FnOnce(&mut Character, u32) -> Option<LevelUp>,
+    {
+        let changed = level::parse_xp(input).map(|xp| change(&mut self.character, xp));
+        match changed {
+            Ok(Some(level_up)) => {
+                info!("{}", level_up);
+                tx_view.send(&Out::LevelUp(level_up));
+                self.level_out(None, tx_view);
             }
-            19 => Ok(640),
-            20 => Ok(700),
-            21 => Ok(810),
-            22 => Ok(970),
-            23 => Ok(1130),
-            24 => Ok(1440),
-            25 => Ok(1750),
-            i => Err(InvalidStrength(i)),
+            Ok(None) => self.level_out(None, tx_view),
+            Err(e) => self.level_out(Some(e), tx_view),
         }
     }
-}
 
-#[derive(Default, Serialize)]
-pub struct Character {
-    pub char_name: String,
-    pub char_class: CharacterClass,
-    pub str: i32,
-    pub str_percentile: Option<i32>,
-    pub dex: i32,
-    pub con: i32,
-    pub int: i32,
-    pub wis: i32,
-    pub cha: i32,
-}
+    fn level_out(&self, err: Option<CharError>, tx_view: &Transmitter<Out>) {
+        tx_view.send(&Out::Level {
+            level: self.character.level(),
+            xp: self.character.xp,
+            next_level_xp: self.character.next_level_xp(),
+            xp_bonus: self.character.xp_bonus(),
+            hit_dice_owed: self.character.hit_dice_owed(),
+            err,
+        });
+    }
 
-impl Character {
-    fn to_json_string(&self) -> Option<String> {
-        Some(serde_json::to_string_pretty(self).ok()?)
+    /// Parses one of the protection fields and stores it, flagging the field if it can't be read
+    fn update_protection<T, P, F>(
+        &mut self,
+        id: &'static str,
+        input: &str,
+        parse: P,
+        set: F,
+        tx_view: &Transmitter<Out>,
+    ) where
+        P: FnOnce(&str) -> Result<T>,
+        F: FnOnce(&mut Protection, T),
+    {
+        let changed = parse(input).map(|val| set(&mut self.character.protection, val));
+        tx_view.send(&Out::ProtectionInput(id, changed.err()));
+    }
+
+    /// Runs an inventory change typed into the sheet and shows the inventory afterwards
+    fn update_inventory<F>(&mut self, tx_view: &Transmitter<Out>, change: F)
+    where
+        F: FnOnce(&mut Character) -> Result<()>,
+    {
+        let err = change(&mut self.character).err();
+        tx_view.send(&Out::Inventory(self.character.inventory.clone(), err));
+        // wearing armor or a shield changes the protection
+        tx_view.send(&Out::Protection(self.character.protection.clone()));
+    }
+
+    /// Runs a change to the purse typed into the field with `id`, then shows the purse
+    fn update_purse<F>(&mut self, id: &'static str, tx_view: &Transmitter<Out>, change: F)
+    where
+        F: FnOnce(&mut Purse) -> Result<()>,
+    {
+        let err = change(&mut self.character.purse).err();
+        tx_view.send(&Out::PurseInput(id, err));
+        tx_view.send(&Out::Purse(self.character.purse.clone()));
+    }
+
+    fn combat_out(&self, tx_view: &Transmitter<Out>) {
+        tx_view.send(&Out::Combat {
+            thac0: self.character.thac0(),
+            melee: self.character.melee_attack(WeaponSkill::Proficient),
+            missile: self.character.missile_attack(WeaponSkill::Proficient),
+            non_proficiency_penalty: self.character.char_class.group().non_proficiency_penalty(),
+            proficiencies: self.character.proficiencies.clone(),
+            weapon_slots: self.character.weapon_slots(),
+            weapon_slots_used: self.character.weapon_slots_used(),
+            weapons: self.character.weapon_attacks(),
+        });
     }
 
-    fn handle_str_update(&mut self, input: &str) -> Result<i32> {
-        let val = input
-            .parse::<i32>()
-            .map_err(|e| CharError::StrParseError(e))?;
-        if (1..=25).contains(&val) {
-            self.str = val;
-            Ok(val)
-        } else {
-            Err(CharError::InvalidStrength(val))
+    /// Shows the rolled scores with their current assignment and whether it can be used
+    fn generated_out(&self, tx_view: &Transmitter<Out>) {
+        if let Some((generated, assignment)) = &self.generated {
+            let err = generated.scores(assignment).err();
+            tx_view.send(&Out::Generated(generated.clone(), assignment.clone(), err));
         }
     }
 
-    fn handle_str_percentile_update(&mut self, input: &str) -> Result<Option<i32>> {
-        if input == "" {
-            self.str_percentile = None;
-            return Ok(None);
+    /// Feeds the arranged scores through the same path as typing them in, then rolls exceptional
+    /// Strength for warriors
+    fn use_generated(&mut self, tx_view: &Transmitter<Out>) -> Result<()> {
+        let (generated, assignment) = match &self.generated {
+            Some(g) => g.clone(),
+            None => return Ok(()),
+        };
+        let scores = generated.scores(&assignment)?;
+        for (ability, score) in Ability::all().into_iter().zip(scores) {
+            self.update_ability(ability, &score.to_string(), tx_view);
         }
+        self.character.generation_method = Some(generated.method);
+        self.character.roll_exceptional_strength(&mut CryptoRng::open()?);
+        tx_view.send(&self.ability_out(
+            Ability::Str,
+            self.character.validate_ability(Ability::Str).err(),
+        ));
+        tx_view.send(&Out::Loaded(self.character.clone()));
+        Ok(())
+    }
 
-        let val = input
-            .parse::<i32>()
-            .map_err(|e| CharError::PercentParseError(e))?;
+    fn update_ability(&mut self, ability: Ability, input: &str, tx_view: &Transmitter<Out>) {
+        match self.character.handle_ability_update(ability, input) {
+            Ok(val) => {
+                info!("updated {} to {}", ability, val);
+                tx_view.send(&self.ability_out(ability, None))
+            }
+            Err(e) => tx_view.send(&self.ability_out(ability, Some(e))),
+        }
+    }
+}
 
-        self.str_percentile = Some(val);
+/// Fills the protection fields from the model
+fn set_protection_values(protection: &Protection) {
+    let bonus = |val: i32| if val == 0 { String::new() } else { val.to_string() };
+    set_select_value("armor", &protection.armor.to_string());
+    set_input_value("armor_bonus", &bonus(protection.armor_bonus));
+    set_select_value("shield", &protection.shield.to_string());
+    set_input_value("shield_bonus", &bonus(protection.shield_bonus));
+    set_input_value(
+        "rings",
+        &protection
+            .rings
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    set_input_value("cloak", &bonus(protection.cloak));
+}
 
-        if (1..=100).contains(&val) {
-            Ok(Some(val))
-        } else {
-            Err(CharError::InvalidPercentile(Some(val)))
-        }
+/// Id of the purse field holding `coin`
+fn coin_field(coin: Coin) -> &'static str {
+    match coin {
+        Coin::Copper => "coins_cp",
+        Coin::Silver => "coins_sp",
+        Coin::Electrum => "coins_ep",
+        Coin::Gold => "coins_gp",
+        Coin::Platinum => "coins_pp",
     }
 }
 
-impl Component for Character {
+/// Inventory indexes come from the values of the inventory select, so anything else is a bug
+fn parse_item_index(input: &str) -> Result<usize> {
+    input
+        .parse::<usize>()
+        .map_err(|_| CharError::UnknownItem(input.to_string()))
+}
+
+impl Component for CharacterSheet {
     type ModelMsg = In;
     type ViewMsg = Out;
 
@@ -280,107 +421,294 @@ impl Component for Character {
         tx_view: &Transmitter<Self::ViewMsg>,
         _sub: &Subscriber<Self::ModelMsg>,
     ) {
+        let name = self.character.char_name.clone();
+        if matches!(
+            msg,
+            In::Str(_)
+                | In::StrPercentile(_)
+                | In::Dex(_)
+                | In::Con(_)
+                | In::Int(_)
+                | In::Wis(_)
+                | In::Cha(_)
+        ) {
+            // scores typed in by hand weren't rolled
+            self.character.generation_method = None;
+        }
         match msg {
             In::CharName(input) => {
-                self.char_name = input.clone();
+                self.character.char_name = input.clone();
             },
             In::CharClass(input) => {
                 match input.parse::<CharacterClass>() {
                     Ok(cc) => {
-                        self.char_class = cc;
+                        self.character.char_class = cc;
                         tx_view.send(&Out::CharClass(cc));
+                        tx_view.send(&Out::ClassAllowed(self.character.validate_class().err()));
+                        // class requirements and the hp adjustment depend on the class
+                        self.revalidate_abilities(tx_view);
                     },
                     Err(err) => {
                         error!("I'm not sure what to do with class:{} with error:{} so I'm just going to ignore it.", input, err)
                     }
                 }
             },
-            In::Str(input) => {
-                match self.handle_str_update(input.as_str()) {
-                    Ok(input) => {
-                        info!("updated str to {}", input);
-                        tx_view.send(&Out::StrPercentile(StrengthPercentile{str: self.str, per: self.str_percentile, err: None}))
+            In::Race(input) => {
+                match input.parse::<Race>() {
+                    Ok(race) => {
+                        self.character.race = race;
+                        tx_view.send(&Out::Race(race));
+                        tx_view.send(&Out::ClassAllowed(self.character.validate_class().err()));
+                        // racial adjustments and limits change every score
+                        self.revalidate_abilities(tx_view);
                     },
-                    Err(e) => {
-                        tx_view.send(&Out::StrPercentile(StrengthPercentile{str: self.str, per: self.str_percentile, err: Some(e)}))
+                    Err(err) => {
+                        error!("I'm not sure what to do with race:{} with error:{} so I'm just going to ignore it.", input, err)
                     }
                 }
             },
+            In::Str(input) => self.update_ability(Ability::Str, input, tx_view),
             In::StrPercentile(input) => {
-                match self.handle_str_percentile_update(input.as_str()) {
+                match self.character.handle_str_percentile_update(input.as_str()) {
                     Ok(input) => {
                         info!("updated str_percentile to {:?}", input);
-                        tx_view.send(&Out::StrPercentile(StrengthPercentile{str: self.str, per: self.str_percentile, err: None}))
+                        tx_view.send(&self.ability_out(Ability::Str, None))
                     },
                     Err(e) => {
-                        tx_view.send(&Out::StrPercentile(StrengthPercentile{str: self.str, per: self.str_percentile, err: Some(e)}))
+                        tx_view.send(&self.ability_out(Ability::Str, Some(e)))
                     }
                 }
             },
-            In::Dex(input) => {
-                match input.parse::<i32>() {
-                    Ok(input) => {
-                        info!("updated dex to {}", input);
-                        self.dex = input;
-                        tx_view.send(&Out::Dex(Some(input)))
-                    },
-                    Err(_) => {
-                        tx_view.send(&Out::Dex(None))
+            In::Dex(input) => self.update_ability(Ability::Dex, input, tx_view),
+            In::Con(input) => self.update_ability(Ability::Con, input, tx_view),
+            In::Int(input) => self.update_ability(Ability::Int, input, tx_view),
+            In::Wis(input) => self.update_ability(Ability::Wis, input, tx_view),
+            In::Cha(input) => self.update_ability(Ability::Cha, input, tx_view),
+            In::Import(json) => {
+                match Character::from_json_str(json) {
+                    Ok((mut character, errors)) => {
+                        info!("imported character {}", character.char_name);
+                        // the import replaces the character in the current roster slot
+                        character.save_id = self.character.save_id;
+                        self.load(character, tx_view);
+                        tx_view.send(&Out::ImportErrors(errors));
                     },
+                    Err(errors) => {
+                        tx_view.send(&Out::ImportErrors(errors));
+                    }
                 }
             },
-            In::Con(input) => {
-                match input.parse::<i32>() {
-                    Ok(input) => {
-                        info!("updated con to {}", input);
-                        self.con = input;
-                        tx_view.send(&Out::Con(Some(input)))
-                    },
-                    Err(_) => {
-                        tx_view.send(&Out::Con(None))
-                    },
+            In::Startup => {
+                self.open_saved(tx_view, |roster, _| roster.first_or_create());
+            },
+            In::Open(input) => {
+                match input.parse::<u32>() {
+                    Ok(id) => self.open_saved(tx_view, |_, _| Ok(id)),
+                    Err(err) => {
+                        error!("I'm not sure what to do with saved character:{} with error:{} so I'm just going to ignore it.", input, err)
+                    }
                 }
             },
-            In::Int(input) => {
-                match input.parse::<i32>() {
-                    Ok(input) => {
-                        info!("updated int to {}", input);
-                        self.int = input;
-                        tx_view.send(&Out::Int(Some(input)))
-                    },
-                    Err(_) => {
-                        tx_view.send(&Out::Int(None))
+            In::New => {
+                self.open_saved(tx_view, |roster, _| roster.create());
+            },
+            In::Duplicate => {
+                self.open_saved(tx_view, |roster, current| match current {
+                    Some(id) => roster.duplicate(id),
+                    None => roster.create(),
+                });
+            },
+            In::Delete => {
+                self.open_saved(tx_view, |roster, current| {
+                    if let Some(id) = current {
+                        roster.delete(id)?;
+                    }
+                    roster.first_or_create()
+                });
+            },
+            In::Generate(input) => {
+                let generated = input.parse::<GenerationMethod>().and_then(|method| {
+                    Ok(method.roll(&mut CryptoRng::open()?))
+                });
+                match generated {
+                    Ok(generated) => {
+                        info!("rolled scores with method {}", generated.method);
+                        let assignment = generated.default_assignment();
+                        self.generated = Some((generated, assignment));
+                        self.generated_out(tx_view);
                     },
+                    Err(err) => {
+                        error!("I'm not sure what to do with method:{} with error:{} so I'm just going to ignore it.", input, err)
+                    }
                 }
             },
-            In::Wis(input) => {
-                match input.parse::<i32>() {
-                    Ok(input) => {
-                        info!("updated wis to {}", input);
-                        self.wis = input;
-                        tx_view.send(&Out::Wis(Some(input)))
-                    },
-                    Err(_) => {
-                        tx_view.send(&Out::Wis(None))
-                    },
+            In::Assign(i, input) => {
+                let ability = Ability::all().into_iter().find(|a| a.field_name() == input);
+                if let (Some((_, assignment)), Some(ability)) = (&mut self.generated, ability) {
+                    if let Some(slot) = assignment.get_mut(*i) {
+                        *slot = ability;
+                    }
                 }
+                self.generated_out(tx_view);
             },
-            In::Cha(input) => {
-                match input.parse::<i32>() {
-                    Ok(input) => {
-                        info!("updated cha to {}", input);
-                        self.cha = input;
-                        tx_view.send(&Out::Cha(Some(input)))
-                    },
-                    Err(_) => {
-                        tx_view.send(&Out::Cha(None))
+            In::UseScores => {
+                if let Err(e) = self.use_generated(tx_view) {
+                    error!("couldn't use the rolled scores: {}", e);
+                }
+            },
+            In::RollHitPoints => {
+                match CryptoRng::open() {
+                    Ok(mut rng) => {
+                        let roll = self.character.roll_hit_points(&mut rng);
+                        info!("rolled {} hit points", roll);
                     },
+                    Err(e) => error!("couldn't roll hit points: {}", e),
+                }
+            },
+            In::Damage(input) => {
+                self.update_hit_points(input, tx_view, |hp, amount| hp.take_damage(amount))
+            },
+            In::Heal(input) => self.update_hit_points(input, tx_view, |hp, amount| hp.heal(amount)),
+            In::TempHp(input) => {
+                self.update_hit_points(input, tx_view, |hp, amount| hp.add_temporary(amount))
+            },
+            In::SetXp(input) => self.update_xp(input, tx_view, |c, xp| c.set_xp(xp)),
+            In::AwardXp(input) => self.update_xp(input, tx_view, |c, xp| c.award_xp(xp)),
+            In::Armor(input) => self.update_protection(
+                "armor",
+                input,
+                str::parse::<ArmorType>,
+                |p, armor| p.armor = armor,
+                tx_view,
+            ),
+            In::ArmorBonus(input) => self.update_protection(
+                "armor_bonus",
+                input,
+                armor_class::parse_bonus,
+                |p, bonus| p.armor_bonus = bonus,
+                tx_view,
+            ),
+            In::Shield(input) => self.update_protection(
+                "shield",
+                input,
+                str::parse::<ShieldType>,
+                |p, shield| p.shield = shield,
+                tx_view,
+            ),
+            In::ShieldBonus(input) => self.update_protection(
+                "shield_bonus",
+                input,
+                armor_class::parse_bonus,
+                |p, bonus| p.shield_bonus = bonus,
+                tx_view,
+            ),
+            In::Rings(input) => self.update_protection(
+                "rings",
+                input,
+                armor_class::parse_bonuses,
+                |p, rings| p.rings = rings,
+                tx_view,
+            ),
+            In::Cloak(input) => self.update_protection(
+                "cloak",
+                input,
+                armor_class::parse_bonus,
+                |p, bonus| p.cloak = bonus,
+                tx_view,
+            ),
+            In::AddItem(name, quantity, location) => self.update_inventory(tx_view, |c| {
+                let quantity = inventory::parse_quantity(quantity)?.unwrap_or(1);
+                c.add_item(name, quantity, location.parse()?).map(|_| ())
+            }),
+            In::RemoveItem(index, quantity) => self.update_inventory(tx_view, |c| {
+                let index = parse_item_index(index)?;
+                c.remove_item(index, inventory::parse_quantity(quantity)?).map(|_| ())
+            }),
+            In::MoveItem(index, location) => self.update_inventory(tx_view, |c| {
+                c.move_item(parse_item_index(index)?, location.parse()?)
+            }),
+            In::BuyItem(name, quantity, location) => {
+                self.update_inventory(tx_view, |c| {
+                    let quantity = inventory::parse_quantity(quantity)?.unwrap_or(1);
+                    c.buy_item(name, quantity, location.parse()?).map(|_| ())
+                });
+                tx_view.send(&Out::Purse(self.character.purse.clone()));
+            }
+            In::Coins(coin, input) => self.update_purse(coin_field(*coin), tx_view, |p| {
+                *p.coins_mut(*coin) = purse::parse_coins(input)?;
+                Ok(())
+            }),
+            In::NormalizeCoins => self.update_purse("coins_gp", tx_view, |p| p.normalize()),
+            In::AddValuable(kind, description, value) => {
+                self.update_purse("valuable_value", tx_view, |p| {
+                    p.valuables.push(Valuable {
+                        kind: kind.parse()?,
+                        description: description.clone(),
+                        value: purse::parse_coins(value)?
+                            .checked_mul(Coin::Gold.value())
+                            .ok_or(CharError::ValueOverflow)?,
+                    });
+                    Ok(())
+                })
+            }
+            In::RemoveValuable(index) => self.update_purse("valuables", tx_view, |p| {
+                let index = index
+                    .parse::<usize>()
+                    .map_err(|_| CharError::ValuableParseError(index.clone()))?;
+                p.remove_valuable(index).map(|_| ())
+            }),
+            In::LearnWeapon(name) => {
+                let learned = self.character.learn_weapon(name);
+                tx_view.send(&Out::ProficiencyChange(learned.err()));
+            }
+            In::ForgetWeapon(name) => {
+                self.character.forget_weapon(name);
+                tx_view.send(&Out::ProficiencyChange(None));
+            }
+            In::Specialize(weapon) => {
+                let specialized = self.character.specialize(weapon);
+                tx_view.send(&Out::ProficiencyChange(specialized.err()));
+            }
+            In::Roll(expr) => {
+                let rolled = CryptoRng::open().and_then(|mut rng| dice::roll(expr, &mut rng));
+                if let Ok(roll) = &rolled {
+                    info!("rolled {}", roll);
                 }
+                tx_view.send(&Out::Rolled(rolled));
             },
         }
         // after changes update render
+        if !matches!(msg, In::Damage(_) | In::Heal(_) | In::TempHp(_)) {
+            // Constitution, class and loading all change hit points
+            self.hit_points_out(None, tx_view);
+        }
+        if !matches!(msg, In::SetXp(_) | In::AwardXp(_)) {
+            // class and prime requisites change the level and the bonus, hit dice what is owed
+            self.level_out(None, tx_view);
+        }
+        // level, class, Strength and Dexterity all feed into the attacks
+        self.combat_out(tx_view);
+        tx_view.send(&Out::SavingThrows(self.character.saving_throws()));
+        tx_view.send(&Out::ArmorClass(self.character.armor_class()));
+        // Strength and what is carried decide the encumbrance
+        tx_view.send(&Out::Encumbrance {
+            load: self.character.load(),
+            encumbrance: self.character.encumbrance(),
+            movement: self.character.movement(),
+        });
         let json_render = self.to_json_string();
         tx_view.send(&Out::JsonRender(json_render));
+        match self.save() {
+            // the roster lists characters by name, so a new name or an import changes it
+            Ok(()) if self.character.char_name != name => {
+                match LocalStorage::open().and_then(|storage| Roster::new(storage).entries()) {
+                    Ok(entries) => tx_view.send(&Out::Roster(entries, self.character.save_id)),
+                    Err(e) => error!("couldn't list saved characters: {}", e),
+                }
+            }
+            Ok(()) => (),
+            Err(e) => error!("couldn't save character: {}", e),
+        }
     }
 
     fn builder(
@@ -394,38 +722,149 @@ impl Component for Character {
                 Out::CharClass(_cc) => {
                     // do something
                 }
+                Out::ClassAllowed(err) => select_error_handler("character_class", err.as_ref()),
+                Out::Race(_race) => {
+                    // handled by the race block
+                }
                 Out::StrPercentile(sp) => {
                     if let Some(e) = &sp.err {
                         match e {
-                            CharError::InvalidStrength(_) | CharError::StrParseError(_) => {
-                                input_error_handler("str", false);
-                            }
                             CharError::PercentParseError(_) | CharError::InvalidPercentile(_) => {
-                                input_error_handler("str_percentile", false);
+                                input_error_handler("str_percentile", Some(e));
+                            }
+                            _ => {
+                                input_error_handler("str", Some(e));
                             }
-                            _ => (),
                         }
                     } else {
-                        input_error_handler("str", true);
-                        input_error_handler("str_percentile", true);
+                        input_error_handler("str", None);
+                        input_error_handler("str_percentile", None);
                     }
                 }
-                Out::Dex(Some(_)) => input_error_handler("dex", true),
-                Out::Dex(None) => input_error_handler("dex", false),
-                Out::Con(Some(_)) => input_error_handler("con", true),
-                Out::Con(None) => input_error_handler("con", false),
-                Out::Int(Some(_)) => input_error_handler("int", true),
-                Out::Int(None) => input_error_handler("int", false),
-                Out::Wis(Some(_)) => input_error_handler("wis", true),
-                Out::Wis(None) => input_error_handler("wis", false),
-                Out::Cha(Some(_)) => input_error_handler("cha", true),
-                Out::Cha(None) => input_error_handler("cha", false),
-                Out::JsonRender(_) => {
+                Out::Dex(d) => input_error_handler("dex", d.err.as_ref()),
+                Out::Con(c) => input_error_handler("con", c.err.as_ref()),
+                Out::Int(i) => input_error_handler("int", i.err.as_ref()),
+                Out::Wis(w) => input_error_handler("wis", w.err.as_ref()),
+                Out::Cha(c) => input_error_handler("cha", c.err.as_ref()),
+                Out::JsonRender(Some(render)) => {
+                    // the textarea stops following its text once it has been edited
+                    set_textarea_value("json_render", render);
+                }
+                Out::JsonRender(None) => {
                     // do nothing
                 }
+                Out::Loaded(c) => {
+                    let score = |val: i32| if val == 0 { String::new() } else { val.to_string() };
+                    set_input_value("character_name", &c.char_name);
+                    set_select_value("character_race", &c.race.to_string());
+                    set_select_value("character_class", &c.char_class.to_string());
+                    set_input_value("str", &score(c.str));
+                    set_input_value(
+                        "str_percentile",
+                        &c.str_percentile.map(|p| p.to_string()).unwrap_or_default(),
+                    );
+                    set_input_value("dex", &score(c.dex));
+                    set_input_value("con", &score(c.con));
+                    set_input_value("int", &score(c.int));
+                    set_input_value("wis", &score(c.wis));
+                    set_input_value("cha", &score(c.cha));
+                    set_protection_values(&c.protection);
+                }
+                Out::Protection(protection) => set_protection_values(protection),
+                Out::Inventory(inventory, err) => {
+                    input_error_handler("item_quantity", err.as_ref());
+                    let options = inventory
+                        .items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, item)| (i.to_string(), item.to_string()))
+                        .collect::<Vec<_>>();
+                    let selected = utils::select_value("inventory_item").unwrap_or_default();
+                    set_select_options("inventory_item", &options, &selected);
+                }
+                Out::Purse(purse) => {
+                    for coin in Coin::all() {
+                        set_input_value(coin_field(coin), &purse.coins(coin).to_string());
+                    }
+                    let options = purse
+                        .valuables
+                        .iter()
+                        .enumerate()
+                        .map(|(i, valuable)| (i.to_string(), valuable.to_string()))
+                        .collect::<Vec<_>>();
+                    set_select_options("valuables", &options, "");
+                }
+                Out::PurseInput(id, err) if *id == "valuables" => {
+                    select_error_handler(id, err.as_ref())
+                }
+                Out::PurseInput(id, err) => input_error_handler(id, err.as_ref()),
+                Out::ImportErrors(_) => {
+                    // shown next to the import controls
+                }
+                Out::Rolled(rolled) => input_error_handler("dice_expr", rolled.as_ref().err()),
+                Out::HitPoints { err, .. } => input_error_handler("hp_amount", err.as_ref()),
+                Out::Level { err, .. } => input_error_handler("xp_amount", err.as_ref()),
+                Out::LevelUp(_) => {
+                    // shown in the experience block
+                }
+                Out::Combat { .. } | Out::SavingThrows(_) | Out::ArmorClass(_) => {
+                    // shown in the combat block
+                }
+                Out::ProficiencyChange(err) => {
+                    select_error_handler("proficiency_weapon", err.as_ref())
+                }
+                Out::Encumbrance { .. } => {
+                    // shown in the inventory block
+                }
+                Out::ProtectionInput(id, err) if *id == "armor" || *id == "shield" => {
+                    select_error_handler(id, err.as_ref())
+                }
+                Out::ProtectionInput(id, err) => input_error_handler(id, err.as_ref()),
+                Out::Generated(_, assignment, _) => {
+                    for (i, ability) in assignment.iter().enumerate() {
+                        set_select_value(&format!("assign_{}", i), ability.field_name());
+                    }
+                }
+                Out::Roster(entries, current) => {
+                    let options = entries
+                        .iter()
+                        .map(|entry| {
+                            let name = if entry.name.is_empty() {
+                                "(unnamed)".to_string()
+                            } else {
+                                entry.name.clone()
+                            };
+                            (entry.id.to_string(), name)
+                        })
+                        .collect::<Vec<_>>();
+                    let selected = current.map(|id| id.to_string()).unwrap_or_default();
+                    set_select_options("roster", &options, &selected);
+                }
             }
         });
 
+        // Saved characters
+        let roster_select = select().id("roster").tx_on(
+            "input",
+            tx.contra_filter_map(|ev: &Event| {
+                let input = utils::event_select_value(ev)?;
+                Some(In::Open(input))
+            }),
+        );
+        let roster_button = |text: &str, msg: In| {
+            button()
+                .attribute("type", "button")
+                .attribute("class", "pure-button")
+                .text(text)
+                .tx_on("click", tx.contra_map(move |_: &Event| msg.clone()))
+        };
+        let roster_bar = div()
+            .attribute("class", "pure-form")
+            .with(roster_select)
+            .with(roster_button("New", In::New))
+            .with(roster_button("Duplicate", In::Duplicate))
+            .with(roster_button("Delete", In::Delete));
+
         // Character Name input field
         let char_name_input = input().tx_on(
             "input",
@@ -435,6 +874,27 @@ impl Component for Character {
             }),
         );
 
+        let class_names = CharacterClass::all()
+            .iter()
+            .map(|cc| cc.to_string())
+            .collect::<Vec<_>>();
+        let mut class_options = vec!["- Select One -"];
+        class_options.extend(class_names.iter().map(|name| name.as_str()));
+
+        let race_names = Race::all()
+            .iter()
+            .map(|race| race.to_string())
+            .collect::<Vec<_>>();
+        let race_options = race_names.iter().map(|name| name.as_str()).collect();
+
+        let race_select = select().tx_on(
+            "input",
+            tx.contra_filter_map(|ev: &Event| {
+                let input = utils::event_select_value(ev)?;
+                Some(In::Race(input))
+            }),
+        );
+
         let class_select = select().tx_on(
             "input",
             tx.contra_filter_map(|ev: &Event| {
@@ -524,18 +984,19 @@ impl Component for Character {
                         "character_name",
                         "Character",
                     ))
+                    // -- Race --
+                    .with(build_form_field_select(
+                        race_select,
+                        "character_race",
+                        "Race",
+                        race_options,
+                    ))
                     // -- Class --
                     .with(build_form_field_select(
                         class_select,
                         "character_class",
                         "Class / Kit",
-                        vec![
-                            "- Select One -",
-                            CharacterClass::Fighter.to_string().as_str(),
-                            CharacterClass::Cleric.to_string().as_str(),
-                            CharacterClass::Wizard.to_string().as_str(),
-                            CharacterClass::Rogue.to_string().as_str(),
-                        ],
+                        class_options,
                     ))
                     // -- Str --
                     .with(build_form_field_input(str_input, "str", "Str"))
@@ -558,6 +1019,7 @@ impl Component for Character {
             );
 
         let json_render = textarea()
+            .id("json_render")
             .attribute("rows", "10")
             .attribute("cols", "50")
             .rx_text(
@@ -571,6 +1033,46 @@ impl Component for Character {
                 }),
             );
 
+        let import_button = button()
+            .attribute("type", "button")
+            .attribute("class", "pure-button")
+            .text("Import")
+            .tx_on(
+                "click",
+                tx.contra_filter_map(|_: &Event| {
+                    let json = utils::textarea_value("json_render")?;
+                    Some(In::Import(json))
+                }),
+            );
+
+        let tx_file = tx.clone();
+        let import_file = input()
+            .attribute("type", "file")
+            .attribute("accept", ".json,application/json")
+            .tx_on(
+                "change",
+                tx.contra_filter_map(move |ev: &Event| {
+                    let tx_file = tx_file.clone();
+                    utils::read_file_input(ev, move |json| tx_file.send(&In::Import(json)));
+                    // the import is sent once the file has been read
+                    None
+                }),
+            );
+
+        let import_errors = p().attribute("class", "import_error").rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::ImportErrors(errors) => Some(
+                    errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                ),
+                _ => None,
+            }),
+        );
+
         let derived_str_scores = vec![
             p().rx_text(
                 "Hit Adj",
@@ -612,60 +1114,1022 @@ impl Component for Character {
                     _ => None,
                 }),
             ),
+            p().rx_text(
+                "Open Doors",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::StrPercentile(s) => match s.open_doors() {
+                        Ok(v) => Some(format!("Open Doors: {}", v)),
+                        Err(e) => Some(format!("Open Doors: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Bend Bars",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::StrPercentile(s) => match s.bend_bars() {
+                        Ok(v) => Some(format!("Bend Bars: {}%", v)),
+                        Err(e) => Some(format!("Bend Bars: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
         ];
 
-        let mut str_scores = div().attribute("class", "pure-g");
-        for gb in derived_str_scores {
-            str_scores = str_scores.with(div().attribute("class", "pure-u-sm-1-6").with(gb));
-        }
+        let derived_dex_scores = vec![
+            p().rx_text(
+                "Reaction Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Dex(d) => match d.reaction_adj() {
+                        Ok(v) => Some(format!("Reaction Adj: {}", v)),
+                        Err(e) => Some(format!("Reaction Adj: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Missile Attack Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Dex(d) => match d.missile_attack_adj() {
+                        Ok(v) => Some(format!("Missile Attack Adj: {}", v)),
+                        Err(e) => Some(format!("Missile Attack Adj: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Defensive Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Dex(d) => match d.defensive_adj() {
+                        Ok(v) => Some(format!("Defensive Adj: {}", v)),
+                        Err(e) => Some(format!("Defensive Adj: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+        ];
 
-        // -- main root --
-        div()
+        let derived_con_scores = vec![
+            p().rx_text(
+                "HP Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Con(c) => match c.hp_adj() {
+                        Ok(v) => Some(format!("HP Adj: {}", v)),
+                        Err(e) => Some(format!("HP Adj: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "System Shock",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Con(c) => match c.system_shock() {
+                        Ok(v) => Some(format!("System Shock: {}%", v)),
+                        Err(e) => Some(format!("System Shock: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Resurrection Survival",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Con(c) => match c.resurrection_survival() {
+                        Ok(v) => Some(format!("Resurrection Survival: {}%", v)),
+                        Err(e) => Some(format!("Resurrection Survival: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Poison Save",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Con(c) => match c.poison_save() {
+                        Ok(v) => Some(format!("Poison Save: {}", v)),
+                        Err(e) => Some(format!("Poison Save: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Regeneration",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Con(c) => match c.regeneration() {
+                        Ok(Some(v)) => Some(format!("Regeneration: 1/{} turns", v)),
+                        Ok(None) => Some("Regeneration: Nil".to_string()),
+                        Err(e) => Some(format!("Regeneration: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+        ];
+
+        let derived_int_scores = vec![
+            p().rx_text(
+                "Languages",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Int(i) => match i.languages() {
+                        Ok(v) => Some(format!("Languages: {}", v)),
+                        Err(e) => Some(format!("Languages: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Spell Level",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Int(i) => match i.max_spell_level() {
+                        Ok(Some(v)) => Some(format!("Spell Level: {}", v)),
+                        Ok(None) => Some("Spell Level: -".to_string()),
+                        Err(e) => Some(format!("Spell Level: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Chance to Learn",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Int(i) => match i.chance_to_learn() {
+                        Ok(Some(v)) => Some(format!("Chance to Learn: {}%", v)),
+                        Ok(None) => Some("Chance to Learn: -".to_string()),
+                        Err(e) => Some(format!("Chance to Learn: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Max Spells/Level",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Int(i) => match i.max_spells_per_level() {
+                        Ok(Some(v)) => Some(format!("Max Spells/Level: {}", v)),
+                        Ok(None) => Some("Max Spells/Level: -".to_string()),
+                        Err(e) => Some(format!("Max Spells/Level: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Illusion Immunity",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Int(i) => match i.illusion_immunity() {
+                        Ok(Some(v)) => Some(format!("Illusion Immunity: {}", v)),
+                        Ok(None) => Some("Illusion Immunity: -".to_string()),
+                        Err(e) => Some(format!("Illusion Immunity: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+        ];
+
+        let derived_wis_scores = vec![
+            p().rx_text(
+                "Magic Defense Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Wis(w) => match w.magical_defense_adj() {
+                        Ok(v) => Some(format!("Magic Defense Adj: {}", v)),
+                        Err(e) => Some(format!("Magic Defense Adj: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Bonus Spells",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Wis(w) => match w.bonus_spells() {
+                        Ok(v) => {
+                            let bonus = v
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, n)| **n > 0)
+                                .map(|(level, n)| format!("L{}x{}", level + 1, n))
+                                .collect::<Vec<_>>();
+                            if bonus.is_empty() {
+                                Some("Bonus Spells: -".to_string())
+                            } else {
+                                Some(format!("Bonus Spells: {}", bonus.join(", ")))
+                            }
+                        }
+                        Err(e) => Some(format!("Bonus Spells: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Spell Failure",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Wis(w) => match w.spell_failure() {
+                        Ok(v) => Some(format!("Spell Failure: {}%", v)),
+                        Err(e) => Some(format!("Spell Failure: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Spell Immunity",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Wis(w) => match w.spell_immunity() {
+                        Ok(v) if v.is_empty() => Some("Spell Immunity: -".to_string()),
+                        Ok(v) => Some(format!("Spell Immunity: {}", v.join(", "))),
+                        Err(e) => Some(format!("Spell Immunity: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+        ];
+
+        let derived_cha_scores = vec![
+            p().rx_text(
+                "Max Henchmen",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Cha(c) => match c.max_henchmen() {
+                        Ok(v) => Some(format!("Max Henchmen: {}", v)),
+                        Err(e) => Some(format!("Max Henchmen: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Loyalty Base",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Cha(c) => match c.loyalty_base() {
+                        Ok(v) => Some(format!("Loyalty Base: {}", v)),
+                        Err(e) => Some(format!("Loyalty Base: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Reaction Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Cha(c) => match c.reaction_adj() {
+                        Ok(v) => Some(format!("Reaction Adj: {}", v)),
+                        Err(e) => Some(format!("Reaction Adj: Err! {:?}", e)),
+                    },
+                    _ => None,
+                }),
+            ),
+        ];
+
+        let derived_race_scores = vec![
+            p().rx_text(
+                "Infravision",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Race(r) => match r.infravision() {
+                        Some(v) => Some(format!("Infravision: {}'", v)),
+                        None => Some("Infravision: -".to_string()),
+                    },
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Ability Adj",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Race(r) => {
+                        let adj = Ability::all()
+                            .into_iter()
+                            .filter(|a| r.ability_adjustment(*a) != 0)
+                            .map(|a| format!("{} {:+}", a, r.ability_adjustment(a)))
+                            .collect::<Vec<_>>();
+                        if adj.is_empty() {
+                            Some("Ability Adj: -".to_string())
+                        } else {
+                            Some(format!("Ability Adj: {}", adj.join(", ")))
+                        }
+                    }
+                    _ => None,
+                }),
+            ),
+            p().rx_text(
+                "Multi-class",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Race(r) => {
+                        let combos = r
+                            .multi_classes()
+                            .iter()
+                            .map(|classes| {
+                                classes
+                                    .iter()
+                                    .map(|cc| cc.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join("/")
+                            })
+                            .collect::<Vec<_>>();
+                        if combos.is_empty() {
+                            Some("Multi-class: -".to_string())
+                        } else {
+                            Some(format!("Multi-class: {}", combos.join(", ")))
+                        }
+                    }
+                    _ => None,
+                }),
+            ),
+        ];
+
+        // Hit points
+        let hp_total = p().rx_text(
+            "Hit Points",
+            rx.branch_filter_map(|ev| match ev {
+                Out::HitPoints {
+                    current,
+                    max,
+                    hit_points,
+                    ..
+                } => {
+                    if hit_points.temporary > 0 {
+                        Some(format!(
+                            "Hit Points: {}/{} ({} temporary)",
+                            current, max, hit_points.temporary
+                        ))
+                    } else {
+                        Some(format!("Hit Points: {}/{}", current, max))
+                    }
+                }
+                _ => None,
+            }),
+        );
+        let hp_rolls = p().rx_text(
+            "Hit Dice",
+            rx.branch_filter_map(|ev| match ev {
+                Out::HitPoints { hit_points, .. } if hit_points.rolls.is_empty() => {
+                    Some("Hit Dice: -".to_string())
+                }
+                Out::HitPoints { hit_points, .. } => Some(format!(
+                    "Hit Dice: {}",
+                    hit_points
+                        .rolls
+                        .iter()
+                        .map(|r| r.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                _ => None,
+            }),
+        );
+        let hp_amount = input().id("hp_amount").attribute("placeholder", "amount");
+        let hp_button = |text: &str, msg: fn(String) -> In| {
+            button()
+                .attribute("type", "button")
+                .attribute("class", "pure-button")
+                .text(text)
+                .tx_on(
+                    "click",
+                    tx.contra_filter_map(move |_: &Event| {
+                        let amount = utils::input_value("hp_amount")?;
+                        Some(msg(amount))
+                    }),
+                )
+        };
+        let roll_hp_button = button()
+            .attribute("type", "button")
+            .attribute("class", "pure-button")
+            .text("Roll Hit Die")
+            .tx_on("click", tx.contra_map(|_: &Event| In::RollHitPoints));
+        let hit_points = div()
+            .with(hp_total)
+            .with(hp_rolls)
             .with(
                 div()
-                    .attribute("class", "pure-g")
-                    .with(div().attribute("class", "pure-u-lg-1-3").with(char_form))
-                    .with(div().attribute("class", "pure-u-lg-1-3").with(str_scores)),
+                    .attribute("class", "pure-form")
+                    .with(hp_amount)
+                    .with(hp_button("Damage", In::Damage))
+                    .with(hp_button("Heal", In::Heal))
+                    .with(hp_button("Temp HP", In::TempHp))
+                    .with(roll_hp_button),
+            );
+
+        // Level and experience
+        let level_total = p().rx_text(
+            "Level",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Level {
+                    level,
+                    xp,
+                    next_level_xp: Some(next),
+                    ..
+                } => Some(format!("Level {} ({} XP, next level at {})", level, xp, next)),
+                Out::Level { level, xp, .. } => Some(format!("Level {} ({} XP)", level, xp)),
+                _ => None,
+            }),
+        );
+        let level_notes = p().rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Level {
+                    xp_bonus,
+                    hit_dice_owed,
+                    ..
+                } => {
+                    let mut notes = vec![];
+                    if *xp_bonus {
+                        notes.push("10% experience bonus".to_string());
+                    }
+                    if *hit_dice_owed > 0 {
+                        notes.push(format!("{} hit dice to roll", hit_dice_owed));
+                    }
+                    Some(notes.join("; "))
+                }
+                _ => None,
+            }),
+        );
+        let level_up = p().rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::LevelUp(level_up) => Some(level_up.to_string()),
+                Out::Loaded(_) => Some(String::new()),
+                _ => None,
+            }),
+        );
+        let xp_amount = input().id("xp_amount").attribute("placeholder", "xp");
+        let xp_button = |text: &str, msg: fn(String) -> In| {
+            button()
+                .attribute("type", "button")
+                .attribute("class", "pure-button")
+                .text(text)
+                .tx_on(
+                    "click",
+                    tx.contra_filter_map(move |_: &Event| {
+                        let amount = utils::input_value("xp_amount")?;
+                        Some(msg(amount))
+                    }),
+                )
+        };
+        let experience = div()
+            .with(level_total)
+            .with(level_notes)
+            .with(level_up)
+            .with(
+                div()
+                    .attribute("class", "pure-form")
+                    .with(xp_amount)
+                    .with(xp_button("Award XP", In::AwardXp))
+                    .with(xp_button("Set XP", In::SetXp)),
+            );
+
+        // Combat
+        let proficiency_select = CATALOG
+            .iter()
+            .filter(|item| catalog::find_weapon(item.name).is_some())
+            .map(|item| (item.name, item.name.to_string()))
+            .chain(
+                WEAPON_GROUPS
+                    .iter()
+                    .map(|group| (group.name, group.to_string())),
             )
-            .with(json_render)
-    }
-}
+            .fold(select().id("proficiency_weapon"), |select, (value, text)| {
+                select.with(option().attribute("value", value).text(&text))
+            });
+        let proficiency_button = |text: &str, msg: fn(String) -> In| {
+            button()
+                .attribute("type", "button")
+                .attribute("class", "pure-button")
+                .text(text)
+                .tx_on(
+                    "click",
+                    tx.contra_filter_map(move |_: &Event| {
+                        Some(msg(utils::select_value("proficiency_weapon")?))
+                    }),
+                )
+        };
+        let proficiency_form = div()
+            .attribute("class", "pure-form")
+            .with(proficiency_select)
+            .with(proficiency_button("Learn", In::LearnWeapon))
+            .with(proficiency_button("Specialize", In::Specialize))
+            .with(proficiency_button("Forget", In::ForgetWeapon));
+        let combat_line = |label: &'static str, f: fn(&Out) -> Option<String>| {
+            p().rx_text(
+                label,
+                rx.branch_filter_map(move |ev| f(ev).map(|text| format!("{}: {}", label, text))),
+            )
+        };
+        let combat = div()
+            .with(combat_line("THAC0", |ev| match ev {
+                Out::Combat { thac0, .. } => Some(thac0.to_string()),
+                _ => None,
+            }))
+            .with(combat_line("Melee", |ev| match ev {
+                Out::Combat { melee, .. } => Some(melee.to_string()),
+                _ => None,
+            }))
+            .with(combat_line("Missile", |ev| match ev {
+                Out::Combat { missile, .. } => Some(missile.to_string()),
+                _ => None,
+            }))
+            .with(combat_line("Non-proficient", |ev| match ev {
+                Out::Combat {
+                    non_proficiency_penalty,
+                    ..
+                } => Some(format!("{:+}", non_proficiency_penalty)),
+                _ => None,
+            }))
+            .with(combat_line("Weapon Proficiencies", |ev| match ev {
+                Out::Combat {
+                    proficiencies,
+                    weapon_slots,
+                    weapon_slots_used,
+                    ..
+                } => Some(format!(
+                    "{} ({} of {} slots used)",
+                    proficiencies, weapon_slots_used, weapon_slots
+                )),
+                _ => None,
+            }))
+            .with(p().rx_text(
+                "",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Combat { weapons, .. } => Some(
+                        weapons
+                            .iter()
+                            .map(|weapon| weapon.to_string())
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    ),
+                    _ => None,
+                }),
+            ))
+            .with(proficiency_form);
+        // Armor Class
+        let armor_names = ArmorType::all()
+            .iter()
+            .map(|armor| armor.to_string())
+            .collect::<Vec<_>>();
+        let shield_names = ShieldType::all()
+            .iter()
+            .map(|shield| shield.to_string())
+            .collect::<Vec<_>>();
+        let protection_select = |msg: fn(String) -> In| {
+            select().tx_on(
+                "input",
+                tx.contra_filter_map(move |ev: &Event| {
+                    let input = utils::event_select_value(ev)?;
+                    Some(msg(input))
+                }),
+            )
+        };
+        let protection_input = |msg: fn(String) -> In| {
+            input().tx_on(
+                "change",
+                tx.contra_filter_map(move |ev: &Event| {
+                    let input = utils::event_input_value(ev)?;
+                    Some(msg(input))
+                }),
+            )
+        };
+        let protection_form = form()
+            .attribute("class", "pure-form pure-form-aligned")
+            .with(
+                fieldset()
+                    .with(build_form_field_select(
+                        protection_select(In::Armor),
+                        "armor",
+                        "Armor",
+                        armor_names.iter().map(|name| name.as_str()).collect(),
+                    ))
+                    .with(build_form_field_input(
+                        protection_input(In::ArmorBonus),
+                        "armor_bonus",
+                        "Armor +",
+                    ))
+                    .with(build_form_field_select(
+                        protection_select(In::Shield),
+                        "shield",
+                        "Shield",
+                        shield_names.iter().map(|name| name.as_str()).collect(),
+                    ))
+                    .with(build_form_field_input(
+                        protection_input(In::ShieldBonus),
+                        "shield_bonus",
+                        "Shield +",
+                    ))
+                    .with(build_form_field_input(
+                        protection_input(In::Rings),
+                        "rings",
+                        "Rings of Prot. +",
+                    ))
+                    .with(build_form_field_input(
+                        protection_input(In::Cloak),
+                        "cloak",
+                        "Cloak of Prot. +",
+                    )),
+            );
+        let ac_line = |label: &'static str, f: fn(&ArmorClass) -> String| {
+            p().rx_text(
+                label,
+                rx.branch_filter_map(move |ev| match ev {
+                    Out::ArmorClass(ac) => Some(format!("{}: {}", label, f(ac))),
+                    _ => None,
+                }),
+            )
+        };
+        let armor_class = div()
+            .with(p().rx_text(
+                "Armor Class",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::ArmorClass(ac) => Some(ac.to_string()),
+                    _ => None,
+                }),
+            ))
+            .with(ac_line("Shieldless", |ac| ac.shieldless().to_string()))
+            .with(ac_line("Rear", |ac| ac.rear().to_string()))
+            .with(ac_line("Surprised", |ac| ac.surprised().to_string()))
+            .with(protection_form);
 
-#[derive(Debug, Clone, Copy, Serialize)]
-pub enum CharacterClass {
-    Fighter,
-    Cleric,
-    Wizard,
-    Rogue,
-}
+        // Inventory
+        let catalog_select = CATALOG.iter().fold(select().id("item_catalog"), |select, item| {
+            select.with(
+                option().attribute("value", item.name).text(&format!(
+                    "{} ({}, {} lb)",
+                    item.name,
+                    catalog::format_cost(item.cost),
+                    item.weight
+                )),
+            )
+        });
+        let location_select = Location::all()
+            .into_iter()
+            .fold(select().id("item_location"), |select, location| {
+                let location = location.to_string();
+                select.with(option().attribute("value", &location).text(&location))
+            });
+        let item_quantity = input()
+            .id("item_quantity")
+            .attribute("placeholder", "quantity");
+        let inventory_button = |text: &str, msg: fn() -> Option<In>| {
+            button()
+                .attribute("type", "button")
+                .attribute("class", "pure-button")
+                .text(text)
+                .tx_on("click", tx.contra_filter_map(move |_: &Event| msg()))
+        };
+        let add_item_button = inventory_button("Add", || {
+            Some(In::AddItem(
+                utils::select_value("item_catalog")?,
+                utils::input_value("item_quantity")?,
+                utils::select_value("item_location")?,
+            ))
+        });
+        let buy_item_button = inventory_button("Buy", || {
+            Some(In::BuyItem(
+                utils::select_value("item_catalog")?,
+                utils::input_value("item_quantity")?,
+                utils::select_value("item_location")?,
+            ))
+        });
+        let remove_item_button = inventory_button("Remove", || {
+            Some(In::RemoveItem(
+                utils::select_value("inventory_item")?,
+                utils::input_value("item_quantity")?,
+            ))
+        });
+        let move_item_button = inventory_button("Move", || {
+            Some(In::MoveItem(
+                utils::select_value("inventory_item")?,
+                utils::select_value("item_location")?,
+            ))
+        });
+        let inventory_total = p().rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Inventory(inventory, _) => Some(format!(
+                    "Inventory: {:.1} lb carried, {:.1} lb on mount, worth {}",
+                    inventory.carried_weight(),
+                    inventory.mount_weight(),
+                    inventory
+                        .value()
+                        .map_or_else(|e| format!("Err! {}", e), catalog::format_cost)
+                )),
+                _ => None,
+            }),
+        );
+        let inventory = div()
+            .with(
+                div()
+                    .attribute("class", "pure-form")
+                    .with(catalog_select)
+                    .with(item_quantity)
+                    .with(location_select)
+                    .with(add_item_button)
+                    .with(buy_item_button),
+            )
+            .with(
+                div()
+                    .attribute("class", "pure-form")
+                    .with(select().id("inventory_item"))
+                    .with(remove_item_button)
+                    .with(move_item_button),
+            )
+            .with(inventory_total)
+            .with(p().rx_text(
+                "",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Encumbrance {
+                        load,
+                        encumbrance,
+                        movement,
+                    } => Some(format!(
+                        "Encumbrance: {} ({:.1} lb), movement {}",
+                        encumbrance, load, movement
+                    )),
+                    _ => None,
+                }),
+            ));
 
-impl FromStr for CharacterClass {
-    type Err = CharError;
+        // Purse
+        let coins_form = Coin::all().into_iter().rev().fold(fieldset(), |fields, coin| {
+            let coin_input = input().tx_on(
+                "change",
+                tx.contra_filter_map(move |ev: &Event| {
+                    let input = utils::event_input_value(ev)?;
+                    Some(In::Coins(coin, input))
+                }),
+            );
+            fields.with(build_form_field_input(
+                coin_input,
+                coin_field(coin),
+                &coin.to_string(),
+            ))
+        });
+        let normalize_button = inventory_button("Exchange", || Some(In::NormalizeCoins));
+        let valuable_kind = ValuableKind::all()
+            .into_iter()
+            .fold(select().id("valuable_kind"), |select, kind| {
+                let kind = kind.to_string();
+                select.with(option().attribute("value", &kind).text(&kind))
+            });
+        let add_valuable_button = inventory_button("Add", || {
+            Some(In::AddValuable(
+                utils::select_value("valuable_kind")?,
+                utils::input_value("valuable_description")?,
+                utils::input_value("valuable_value")?,
+            ))
+        });
+        let remove_valuable_button = inventory_button("Remove", || {
+            Some(In::RemoveValuable(utils::select_value("valuables")?))
+        });
+        let purse = div()
+            .with(
+                form()
+                    .attribute("class", "pure-form pure-form-aligned")
+                    .with(coins_form.with(normalize_button)),
+            )
+            .with(
+                div()
+                    .attribute("class", "pure-form")
+                    .with(valuable_kind)
+                    .with(
+                        input()
+                            .id("valuable_description")
+                            .attribute("placeholder", "description"),
+                    )
+                    .with(
+                        input()
+                            .id("valuable_value")
+                            .attribute("placeholder", "gp"),
+                    )
+                    .with(add_valuable_button),
+            )
+            .with(
+                div()
+                    .attribute("class", "pure-form")
+                    .with(select().id("valuables"))
+                    .with(remove_valuable_button),
+            )
+            .with(p().rx_text(
+                "",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Purse(purse) => Some(format!(
+                        "Purse: {} (worth {}), {:.1} lb, gems and jewelry worth {}",
+                        purse,
+                        purse
+                            .value()
+                            .map_or_else(|e| format!("Err! {}", e), catalog::format_cost),
+                        purse.weight(),
+                        purse
+                            .valuables_value()
+                            .map_or_else(|e| format!("Err! {}", e), catalog::format_cost)
+                    )),
+                    _ => None,
+                }),
+            ))
+            .with(p().rx_text(
+                "",
+                rx.branch_filter_map(|ev| match ev {
+                    Out::Purse(purse) => Some(
+                        purse
+                            .transactions
+                            .iter()
+                            .map(|transaction| transaction.to_string())
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    ),
+                    _ => None,
+                }),
+            ));
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "Fighter" => Ok(CharacterClass::Fighter),
-            "Cleric" => Ok(CharacterClass::Cleric),
-            "Wizard" => Ok(CharacterClass::Wizard),
-            "Rogue" => Ok(CharacterClass::Rogue),
-            _ => Err(CharError::CharacterClassParseError(String::from(s))),
-        }
-    }
-}
+        let saving_throws = (0..SaveCategory::all().len()).fold(div(), |saves, i| {
+            saves.with(p().rx_text(
+                "",
+                rx.branch_filter_map(move |ev| match ev {
+                    Out::SavingThrows(saves) => saves
+                        .get(i)
+                        .map(|save| format!("{}: {}", save.category, save)),
+                    _ => None,
+                }),
+            ))
+        });
 
-impl Display for CharacterClass {
-    fn fmt(&self, f: &mut Formatter) -> std::result::Result<(), std::fmt::Error> {
-        match self {
-            CharacterClass::Fighter => write!(f, "Fighter"),
-            CharacterClass::Cleric => write!(f, "Cleric"),
-            CharacterClass::Wizard => write!(f, "Wizard"),
-            CharacterClass::Rogue => write!(f, "Rogue"),
-        }
-    }
-}
+        // Ability score generation
+        let method_select = GenerationMethod::all().into_iter().fold(
+            select().id("generation_method"),
+            |select, method| {
+                select.with(
+                    option()
+                        .attribute("value", &method.to_string())
+                        .text(&format!("Method {}: {}", method, method.description())),
+                )
+            },
+        );
+        let generate_button = button()
+            .attribute("type", "button")
+            .attribute("class", "pure-button")
+            .text("Roll Scores")
+            .tx_on(
+                "click",
+                tx.contra_filter_map(|_: &Event| {
+                    let method = utils::select_value("generation_method")?;
+                    Some(In::Generate(method))
+                }),
+            );
+        let ability_options = Ability::all()
+            .into_iter()
+            .map(|ability| (ability.field_name(), ability.to_string()))
+            .collect::<Vec<_>>();
+        // enough slots for the seven dice of Method VI, the unused ones are hidden
+        let assign_slots = (0..7).fold(div().attribute("class", "pure-form"), |slots, i| {
+            let assign_select = ability_options.iter().fold(
+                select().id(&format!("assign_{}", i)),
+                |select, (value, text)| select.with(option().attribute("value", value).text(text)),
+            );
+            let slot = span()
+                .rx_boolean_attribute(
+                    "hidden",
+                    true,
+                    rx.branch_filter_map(move |ev| match ev {
+                        Out::Generated(generated, _, _) => Some(i >= generated.values.len()),
+                        _ => None,
+                    }),
+                )
+                .with(label().attribute("for", &format!("assign_{}", i)).rx_text(
+                    "",
+                    rx.branch_filter_map(move |ev| match ev {
+                        Out::Generated(generated, _, _) => {
+                            generated.values.get(i).map(|v| v.to_string())
+                        }
+                        _ => None,
+                    }),
+                ))
+                .with(
+                    assign_select
+                        .rx_boolean_attribute(
+                            "disabled",
+                            false,
+                            rx.branch_filter_map(|ev| match ev {
+                                Out::Generated(generated, _, _) => {
+                                    Some(!generated.method.arranged())
+                                }
+                                _ => None,
+                            }),
+                        )
+                        .tx_on(
+                            "input",
+                            tx.contra_filter_map(move |ev: &Event| {
+                                let input = utils::event_select_value(ev)?;
+                                Some(In::Assign(i, input))
+                            }),
+                        ),
+                );
+            slots.with(slot)
+        });
+        let use_scores_button = button()
+            .attribute("type", "button")
+            .attribute("class", "pure-button")
+            .text("Use Scores")
+            .tx_on("click", tx.contra_map(|_: &Event| In::UseScores));
+        let generated_rolls = p().rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Generated(generated, _, _) => Some(
+                    generated
+                        .rolls
+                        .iter()
+                        .map(|roll| roll.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                ),
+                _ => None,
+            }),
+        );
+        let arrangement_error = p().attribute("class", "import_error").rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Generated(_, _, err) => {
+                    Some(err.as_ref().map(|e| e.to_string()).unwrap_or_default())
+                }
+                _ => None,
+            }),
+        );
 
-impl Default for CharacterClass {
-    fn default() -> Self {
-        CharacterClass::Fighter
+        // Dice roller
+        let dice_input = input()
+            .id("dice_expr")
+            .attribute("placeholder", "4d6kh3")
+            .tx_on(
+                "change",
+                tx.contra_filter_map(|ev: &Event| {
+                    let input = utils::event_input_value(ev)?;
+                    Some(In::Roll(input))
+                }),
+            );
+        let roll_button = button()
+            .attribute("type", "button")
+            .attribute("class", "pure-button")
+            .text("Roll")
+            .tx_on(
+                "click",
+                tx.contra_filter_map(|_: &Event| {
+                    let expr = utils::input_value("dice_expr")?;
+                    Some(In::Roll(expr))
+                }),
+            );
+        let dice_result = p().rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Rolled(Ok(roll)) => Some(roll.to_string()),
+                Out::Rolled(Err(e)) => Some(e.to_string()),
+                _ => None,
+            }),
+        );
+
+        let race_abilities = p().rx_text(
+            "",
+            rx.branch_filter_map(|ev| match ev {
+                Out::Race(r) => Some(r.special_abilities().join("; ")),
+                _ => None,
+            }),
+        );
+
+        // -- main root --
+        div()
+            .with(roster_bar)
+            .with(
+                div()
+                    .attribute("class", "pure-g")
+                    .with(
+                        div()
+                            .attribute("class", "pure-u-lg-1-3")
+                            .with(char_form)
+                            .with(experience)
+                            .with(hit_points)
+                            .with(combat)
+                            .with(armor_class)
+                            .with(saving_throws)
+                            .with(inventory)
+                            .with(purse),
+                    )
+                    .with(
+                        div()
+                            .attribute("class", "pure-u-lg-1-3")
+                            .with(build_derived_scores(derived_race_scores))
+                            .with(race_abilities)
+                            .with(build_derived_scores(derived_str_scores))
+                            .with(build_derived_scores(derived_cha_scores))
+                            .with(build_derived_scores(derived_dex_scores))
+                            .with(build_derived_scores(derived_con_scores))
+                            .with(build_derived_scores(derived_int_scores))
+                            .with(build_derived_scores(derived_wis_scores)),
+                    ),
+            )
+            .with(
+                div()
+                    .with(
+                        div()
+                            .attribute("class", "pure-form")
+                            .with(method_select)
+                            .with(generate_button),
+                    )
+                    .with(assign_slots)
+                    .with(use_scores_button)
+                    .with(generated_rolls)
+                    .with(arrangement_error),
+            )
+            .with(json_render)
+            .with(div().with(import_button).with(import_file))
+            .with(import_errors)
+            .with(
+                div()
+                    .attribute("class", "pure-form")
+                    .with(dice_input)
+                    .with(roll_button)
+                    .with(dice_result),
+            )
     }
 }
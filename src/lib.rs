@@ -1,4 +1,4 @@
-use crate::character_model::Character;
+use crate::character_model::{CharacterSheet, In};
 use log::Level;
 use mogwai::prelude::*;
 use std::panic;
@@ -6,7 +6,8 @@ use std::result::Result;
 use wasm_bindgen::prelude::*;
 
 mod character_model;
-mod common;
+mod rng;
+mod storage;
 mod utils;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
@@ -20,8 +21,10 @@ pub fn main() -> Result<(), JsValue> {
     panic::set_hook(Box::new(console_error_panic_hook::hook));
     console_log::init_with_level(Level::Trace).unwrap();
 
-    let character = Character {
-        ..Default::default()
-    };
-    character.into_component().run()
+    let component = CharacterSheet::default().into_component();
+    let tx = component.trns.clone();
+    component.run()?;
+    // pick up the saved characters once the sheet is on the page
+    tx.send(&In::Startup);
+    Ok(())
 }
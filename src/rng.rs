@@ -0,0 +1,28 @@
+use charsheet_core::common::{CharError, Result};
+use charsheet_core::dice::Rng;
+use mogwai::utils as mogwaiutils;
+
+/// Dice rolls from the browser's `crypto.getRandomValues`
+pub struct CryptoRng {
+    crypto: web_sys::Crypto,
+}
+
+impl CryptoRng {
+    pub fn open() -> Result<CryptoRng> {
+        let crypto = mogwaiutils::window()
+            .crypto()
+            .map_err(|e| CharError::RngError(format!("{:?}", e)))?;
+        Ok(CryptoRng { crypto })
+    }
+}
+
+impl Rng for CryptoRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        // only fails for requests over 64k, four bytes always fit
+        self.crypto
+            .get_random_values_with_u8_array(&mut bytes)
+            .expect("crypto.getRandomValues failed");
+        u32::from_le_bytes(bytes)
+    }
+}
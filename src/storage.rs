@@ -0,0 +1,36 @@
+use charsheet_core::common::{CharError, Result};
+use charsheet_core::storage::Storage;
+use mogwai::utils as mogwaiutils;
+
+/// The browser's `localStorage`
+pub struct LocalStorage {
+    storage: web_sys::Storage,
+}
+
+impl LocalStorage {
+    pub fn open() -> Result<LocalStorage> {
+        let storage = mogwaiutils::window()
+            .local_storage()
+            .map_err(|e| CharError::StorageError(format!("{:?}", e)))?
+            .ok_or_else(|| CharError::StorageError("localStorage is not available".to_string()))?;
+        Ok(LocalStorage { storage })
+    }
+}
+
+impl Storage for LocalStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        self.storage.get_item(key).ok()?
+    }
+
+    fn set_item(&mut self, key: &str, value: &str) -> Result<()> {
+        self.storage
+            .set_item(key, value)
+            .map_err(|e| CharError::StorageError(format!("{:?}", e)))
+    }
+
+    fn remove_item(&mut self, key: &str) -> Result<()> {
+        self.storage
+            .remove_item(key)
+            .map_err(|e| CharError::StorageError(format!("{:?}", e)))
+    }
+}
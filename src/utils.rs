@@ -1,7 +1,9 @@
+use charsheet_core::common::CharError;
 use log::error;
 use mogwai::prelude::*;
 use mogwai::utils as mogwaiutils;
-use web_sys::HtmlSelectElement;
+use wasm_bindgen::closure::Closure;
+use web_sys::{FileReader, HtmlOptionElement, HtmlSelectElement, HtmlTextAreaElement};
 
 /// Extracts the value from an HTML input field from the given HTML Event
 pub fn event_input_value(ev: &Event) -> Option<String> {
@@ -15,8 +17,116 @@ pub fn event_select_value(ev: &Event) -> Option<String> {
     Some(input.value().trim().to_string())
 }
 
-/// Sets `:invalid` pseudo css class on an element with `element_id`
-pub fn input_error_handler(element_id: &str, is_valid: bool) {
+/// Sets the value of the `input` element with `element_id`
+pub fn set_input_value(element_id: &str, value: &str) {
+    match mogwaiutils::document()
+        .get_element_by_id(element_id)
+        .map(|el| el.dyn_into::<HtmlInputElement>())
+    {
+        Some(Ok(element)) => element.set_value(value),
+        _ => error!("couldn't find input element:{}", element_id),
+    }
+}
+
+/// Selects the option with `value` in the `select` element with `element_id`
+pub fn set_select_value(element_id: &str, value: &str) {
+    match mogwaiutils::document()
+        .get_element_by_id(element_id)
+        .map(|el| el.dyn_into::<HtmlSelectElement>())
+    {
+        Some(Ok(element)) => element.set_value(value),
+        _ => error!("couldn't find select element:{}", element_id),
+    }
+}
+
+/// Replaces the options of the `select` element with `element_id` with `(value, text)` pairs
+/// and selects the option with `selected` as its value
+pub fn set_select_options(element_id: &str, options: &[(String, String)], selected: &str) {
+    let select = match mogwaiutils::document()
+        .get_element_by_id(element_id)
+        .map(|el| el.dyn_into::<HtmlSelectElement>())
+    {
+        Some(Ok(element)) => element,
+        _ => {
+            error!("couldn't find select element:{}", element_id);
+            return;
+        }
+    };
+    select.set_length(0);
+    for (value, text) in options {
+        match HtmlOptionElement::new_with_text_and_value(text, value) {
+            Ok(opt) => {
+                if let Err(e) = select.add_with_html_option_element(&opt) {
+                    error!("couldn't add option:{} error:{:?}", value, e);
+                }
+            }
+            Err(e) => error!("couldn't create option:{} error:{:?}", value, e),
+        }
+    }
+    select.set_value(selected);
+}
+
+/// Sets the value of the `textarea` element with `element_id`
+pub fn set_textarea_value(element_id: &str, value: &str) {
+    match mogwaiutils::document()
+        .get_element_by_id(element_id)
+        .map(|el| el.dyn_into::<HtmlTextAreaElement>())
+    {
+        Some(Ok(element)) => element.set_value(value),
+        _ => error!("couldn't find textarea element:{}", element_id),
+    }
+}
+
+/// Reads the value of the `input` element with `element_id`
+pub fn input_value(element_id: &str) -> Option<String> {
+    let input: HtmlInputElement = mogwaiutils::document()
+        .get_element_by_id(element_id)?
+        .dyn_into()
+        .ok()?;
+    Some(input.value().trim().to_string())
+}
+
+/// Reads the value of the `select` element with `element_id`
+pub fn select_value(element_id: &str) -> Option<String> {
+    let select: HtmlSelectElement = mogwaiutils::document()
+        .get_element_by_id(element_id)?
+        .dyn_into()
+        .ok()?;
+    Some(select.value())
+}
+
+/// Reads the value of the `textarea` element with `element_id`
+pub fn textarea_value(element_id: &str) -> Option<String> {
+    let textarea: HtmlTextAreaElement = mogwaiutils::document()
+        .get_element_by_id(element_id)?
+        .dyn_into()
+        .ok()?;
+    Some(textarea.value())
+}
+
+/// Reads the first file picked in the file `input` that fired `ev` as text and hands it to
+/// `on_load` once the browser is done reading it
+pub fn read_file_input<F>(ev: &Event, on_load: F) -> Option<()>
+where
+    F: FnOnce(String) + 'static,
+{
+    let input: HtmlInputElement = ev.target()?.dyn_into().ok()?;
+    let file = input.files()?.get(0)?;
+    let reader = FileReader::new().ok()?;
+    let result_reader = reader.clone();
+    let onload = Closure::once_into_js(move || {
+        match result_reader.result().ok().and_then(|r| r.as_string()) {
+            Some(text) => on_load(text),
+            None => error!("couldn't read file as text"),
+        }
+    });
+    reader.set_onload(Some(onload.unchecked_ref()));
+    reader.read_as_text(&file).ok()
+}
+
+/// Sets `:invalid` pseudo css class on an element with `element_id`, using the error as the
+/// validation message. Passing `None` clears it.
+pub fn input_error_handler(element_id: &str, err: Option<&CharError>) {
     let input = mogwaiutils::document()
         // TODO: remove expect and pass error up
         .get_element_by_id(element_id)
@@ -24,20 +134,31 @@ pub fn input_error_handler(element_id: &str, is_valid: bool) {
         .dyn_into::<HtmlInputElement>();
 
     match input {
-        Ok(element) => {
-            if is_valid {
-                element.set_custom_validity("");
-            } else {
-                // TODO: set a good custom string
-                element.set_custom_validity("invalid input");
-            }
-        }
+        Ok(element) => match err {
+            Some(e) => element.set_custom_validity(e.to_string().as_str()),
+            None => element.set_custom_validity(""),
+        },
         Err(e) => {
             error!("couldn't find element:{} error:{:?}", element_id, e);
         }
     }
 }
 
+/// Sets `:invalid` pseudo css class on a `select` element with `element_id`, using the error as
+/// the validation message. Passing `None` clears it.
+pub fn select_error_handler(element_id: &str, err: Option<&CharError>) {
+    match mogwaiutils::document()
+        .get_element_by_id(element_id)
+        .map(|el| el.dyn_into::<HtmlSelectElement>())
+    {
+        Some(Ok(element)) => match err {
+            Some(e) => element.set_custom_validity(e.to_string().as_str()),
+            None => element.set_custom_validity(""),
+        },
+        _ => error!("couldn't find select element:{}", element_id),
+    }
+}
+
 /// Utility method: Wraps an input field with div and supplies css for formatting
 pub fn build_form_field_input(input: GizmoBuilder, id: &str, name: &str) -> GizmoBuilder {
     div()
@@ -64,3 +185,12 @@ pub fn build_form_field_select(
         .with(label().attribute("for", id).text(name))
         .with(select)
 }
+
+/// Utility method: Lays out derived score elements in a row of a `pure-g` grid
+pub fn build_derived_scores(scores: Vec<GizmoBuilder>) -> GizmoBuilder {
+    let mut grid = div().attribute("class", "pure-g");
+    for gb in scores {
+        grid = grid.with(div().attribute("class", "pure-u-sm-1-6").with(gb));
+    }
+    grid
+}